@@ -38,6 +38,11 @@ where
 	    entry_index,
 	    visibility,
 	}) => {
+	    // Private entries are not published so only their author has the content to validate
+	    if *visibility == EntryVisibility::Private && register_update.new_entry.is_none() {
+		return Ok( None );
+	    }
+
 	    Some(match &register_update.new_entry {
 		None => Err( guest_err(format!("New entry is None meaning visibility is Private: {:?}", visibility )) )?,
 		Some(entry) => {
@@ -61,6 +66,11 @@ where
 	    entry_index,
 	    visibility,
 	}) => {
+	    // Private entries are not published so only their author has the content to validate
+	    if *visibility == EntryVisibility::Private && register_delete.original_entry.is_none() {
+		return Ok( None );
+	    }
+
 	    Some(match &register_delete.original_entry {
 		None => Err( guest_err(format!("Original entry is None meaning visibility is Private: {:?}", visibility )) )?,
 		Some(entry) => {
@@ -99,6 +109,23 @@ where
     ( path, hash )
 }
 
+pub fn delete_links_to<T,L>( base: &EntryHash, link_type: L, target: &T ) -> ExternResult<Vec<ActionHash>>
+where
+    T: Clone + Into<AnyLinkableHash>,
+    L: LinkTypeFilterExt,
+{
+    let target : AnyLinkableHash = target.to_owned().into();
+    let mut deleted = vec![];
+
+    for link in get_links( base.to_owned(), link_type, None )? {
+	if link.target == target {
+	    deleted.push( delete_link( link.create_link_hash )? );
+	}
+    }
+
+    Ok( deleted )
+}
+
 
 pub fn agentpubkey () -> ExternResult<AgentPubKey> {
     Ok( agent_info()?.agent_initial_pubkey )
@@ -4,8 +4,13 @@
 //! - Cargo package - [crates.io/crates/mere_memory_types](https://crates.io/crates/mere_memory_types)
 //!
 
+use std::cell::Cell;
+use std::io::{ self, Read, Write };
+use std::rc::Rc;
 use hdi::prelude::*;
 use sha2::{ Sha256, Digest };
+use flate2::{ Compression, read::GzDecoder, write::GzEncoder };
+use ruzstd::{ decoding::StreamingDecoder, encoding::{ compress_to_vec, CompressionLevel } };
 
 
 /// Get the hash of the given bytes as a hex string
@@ -17,12 +22,93 @@ pub fn calculate_hash(bytes: &Vec<u8>) -> [u8; 32] {
     hasher.finalize().into()
 }
 
+fn hex_digest(digest: [u8; 32]) -> String {
+    digest.iter()
+        .map( |byte| format!("{:02x}", byte ) )
+        .collect()
+}
+
+
+//
+// Compression
+//
+/// The codec used to compress a byte-set before it is split into MemoryBlockEntry's
+///
+/// Both codecs are pure-Rust implementations so they can be used inside a Zome.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum CompressionCodec {
+    Gzip,
+    Zstd,
+}
+
+impl CompressionCodec {
+    /// Compress the given bytes using this codec
+    pub fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            CompressionCodec::Gzip => {
+                let mut encoder = GzEncoder::new( Vec::new(), Compression::default() );
+
+                encoder.write_all( bytes )
+                    .map_err( |e| format!("Failed to gzip bytes: {}", e ) )?;
+                encoder.finish()
+                    .map_err( |e| format!("Failed to gzip bytes: {}", e ) )
+            },
+            CompressionCodec::Zstd => {
+                Ok( compress_to_vec( bytes, CompressionLevel::Fastest ) )
+            },
+        }
+    }
+
+    /// Wrap a reader of compressed bytes so that reading it yields the decompressed bytes
+    pub fn decoder<'a, R: Read + 'a>(&self, reader: R) -> Result<Box<dyn Read + 'a>, String> {
+        Ok( match self {
+            CompressionCodec::Gzip => Box::new( GzDecoder::new( reader ) ),
+            CompressionCodec::Zstd => Box::new(
+                StreamingDecoder::new( reader )
+                    .map_err( |e| format!("Failed to read zstd frame: {}", e ) )?
+            ),
+        })
+    }
+
+    /// Decompress the given bytes using this codec
+    pub fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        let mut decompressed = Vec::new();
+
+        self.decoder( bytes )?
+            .read_to_end( &mut decompressed )
+            .map_err( |e| format!("Failed to decompress {:?} bytes: {}", self, e ) )?;
+
+        Ok( decompressed )
+    }
+
+    /// Decompress the given bytes, failing as soon as the output exceeds `limit` bytes
+    ///
+    /// Use this for untrusted input so that a small payload cannot expand without bound.
+    pub fn decompress_with_limit(&self, bytes: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+        let mut decompressed = Vec::new();
+
+        self.decoder( bytes )?
+            .take( limit.saturating_add( 1 ) )
+            .read_to_end( &mut decompressed )
+            .map_err( |e| format!("Failed to decompress {:?} bytes: {}", self, e ) )?;
+
+        if decompressed.len() as u64 > limit {
+            return Err( format!("Decompressed {:?} bytes exceed the limit of {} bytes", self, limit ) );
+        }
+
+        Ok( decompressed )
+    }
+}
+
 
 //
 // Memory Entry
 //
 /// An Entry that represents a full byte-set by grouping a set of MemoryBlockEntry
 ///
+/// The `hash` and `memory_size` always describe the uncompressed byte-set.  When `compression` is
+/// set, the bytes spread across `block_addresses` are the compressed form.
+///
 /// Example values
 /// ```ignore
 /// use mere_memory_types::{ MemoryEntry };
@@ -35,6 +121,7 @@ pub fn calculate_hash(bytes: &Vec<u8>) -> [u8; 32] {
 ///     block_addresses: vec![
 ///         EntryHash::try_from("uhCEkBh2fW3K2RE41X3MOO3LdrMUYPPXWPGtuDjwRrXQZk-94N7Ku").unwrap(),
 ///     ],
+///     compression: None,
 /// };
 /// ```
 #[hdk_entry_helper]
@@ -45,6 +132,104 @@ pub struct MemoryEntry {
     pub hash: String,
     pub memory_size: u64,
     pub block_addresses: Vec<EntryHash>,
+
+    // optional
+    #[serde(default)]
+    pub compression: Option<CompressionCodec>,
+}
+
+
+/// Counts the bytes read through it so decompression can be bounded by how much input it used
+struct CountingReader<R: Read> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read( buf )?;
+        self.count.set( self.count.get() + read as u64 );
+
+        Ok( read )
+    }
+}
+
+const VERIFY_CHUNK_SIZE: usize = 64 * 1024;
+
+
+impl MemoryEntry {
+    /// Check a stream of block bytes against `memory_size` and `hash` without buffering the byte-set
+    ///
+    /// When `compression` is set, decompression stops as soon as the output exceeds `memory_size`
+    /// or `ratio_limit` times the number of block bytes read so far.
+    pub fn verify_stream<'a, R: Read + 'a>(&self, blocks: R, ratio_limit: u64) -> Result<(), String> {
+        let consumed = Rc::new( Cell::new( 0 ) );
+        let counter = CountingReader {
+            inner: blocks,
+            count: consumed.clone(),
+        };
+        let mut reader : Box<dyn Read + 'a> = match self.compression {
+            Some(ref codec) => codec.decoder( counter )?,
+            None => Box::new( counter ),
+        };
+
+        let mut hasher = Sha256::new();
+        let mut chunk = vec![ 0; VERIFY_CHUNK_SIZE ];
+        let mut size : u64 = 0;
+
+        loop {
+            let read = reader.read( &mut chunk )
+                .map_err( |e| format!("Failed to read memory bytes: {}", e ) )?;
+            if read == 0 {
+                break;
+            }
+            size += read as u64;
+
+            if size > self.memory_size {
+                return Err( format!("Memory bytes exceed their recorded size of {} bytes", self.memory_size ) );
+            }
+            if self.compression.is_some() && size > consumed.get().saturating_mul( ratio_limit ) {
+                return Err( format!("Memory bytes expand beyond {}:1 of their compressed size", ratio_limit ) );
+            }
+
+            hasher.update( &chunk[..read] );
+        }
+
+        if size != self.memory_size {
+            return Err( format!("Memory bytes do not match their recorded size: {} != {}", size, self.memory_size ) );
+        }
+
+        let hash = hex_digest( hasher.finalize().into() );
+
+        if hash != self.hash {
+            return Err( format!("Memory bytes do not match their recorded hash: {} != {}", hash, self.hash ) );
+        }
+
+        Ok(())
+    }
+
+    /// Decompress the reassembled block bytes and check them against `memory_size` and `hash`
+    ///
+    /// Decompression never produces more than `memory_size` bytes, so the result is bounded by the
+    /// size this entry claims.
+    pub fn decode_bytes(&self, bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+        let bytes = match self.compression {
+            Some(ref codec) => codec.decompress_with_limit( &bytes, self.memory_size )?,
+            None => bytes,
+        };
+
+        if bytes.len() as u64 != self.memory_size {
+            return Err( format!("Memory bytes do not match their recorded size: {} != {}", bytes.len(), self.memory_size ) );
+        }
+
+        let hash = hex_digest( calculate_hash( &bytes ) );
+
+        if hash != self.hash {
+            return Err( format!("Memory bytes do not match their recorded hash: {} != {}", hash, self.hash ) );
+        }
+
+        Ok( bytes )
+    }
 }
 
 
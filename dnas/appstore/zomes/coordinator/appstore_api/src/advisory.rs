@@ -0,0 +1,170 @@
+use crate::{release, AppResult, ANCHOR_ADVISORIES, ANCHOR_APPS};
+use appstore::{
+    AdvisoryEntry, AdvisorySeverity, EntityId, GetEntityInput, LinkTypes, ReleaseEntry,
+    ReleaseHashes, UpdateEntityInput,
+};
+use hc_crud::{create_entity, get_entities, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+use std::collections::BTreeMap;
+
+fn advisories_path() -> EntryHash {
+    let (_, pathhash) = hc_utils::path_base(ANCHOR_ADVISORIES);
+
+    pathhash
+}
+
+fn app_advisories_path(app: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_ADVISORIES.to_string()],
+    );
+
+    pathhash
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub app: EntityId,
+    pub severity: AdvisorySeverity,
+    pub affected_versions: Vec<String>,
+    pub description: String,
+
+    // optional
+    pub fixed_version: Option<String>,
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<AdvisoryEntry>> {
+    debug!("Creating Advisory for App: {}", input.app);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+
+    let advisory = AdvisoryEntry {
+        app: input.app.clone(),
+        severity: input.severity,
+        affected_versions: input.affected_versions,
+        fixed_version: input.fixed_version,
+        description: input.description,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&advisory)?;
+
+    // Path via App's Advisories
+    entity.link_from(&app_advisories_path(&input.app), LinkTypes::Advisory, None)?;
+    // Path via All Advisories
+    entity.link_from(&advisories_path(), LinkTypes::Advisory, None)?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<AdvisoryEntry>> {
+    debug!("Get advisory: {}", input.id);
+    let entity: Entity<AdvisoryEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub severity: Option<AdvisorySeverity>,
+    pub affected_versions: Option<Vec<String>>,
+    pub fixed_version: Option<String>,
+    pub description: Option<String>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<AdvisoryEntry>> {
+    debug!("Updating Advisory: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+
+    let entity = update_entity(&input.base, |mut current: AdvisoryEntry, _| {
+        current.severity = props.severity.unwrap_or(current.severity);
+        current.affected_versions = props.affected_versions.unwrap_or(current.affected_versions);
+        current.fixed_version = props.fixed_version.or(current.fixed_version);
+        current.description = props.description.unwrap_or(current.description);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Advisory: {}", input.id);
+    let entity: Entity<AdvisoryEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    hc_utils::delete_links_to(
+        &app_advisories_path(&entity.content.app),
+        LinkTypes::Advisory,
+        &input.id,
+    )?;
+    hc_utils::delete_links_to(&advisories_path(), LinkTypes::Advisory, &input.id)?;
+
+    Ok(delete_hash)
+}
+
+/// Gets an app's advisories, most severe first
+pub fn get_for_app(app: &EntityId) -> AppResult<Vec<Entity<AdvisoryEntry>>> {
+    let mut advisories: Vec<Entity<AdvisoryEntry>> =
+        get_entities(&app_advisories_path(app), LinkTypes::Advisory, None)?;
+    advisories.sort_by(|a, b| b.content.severity.cmp(&a.content.severity));
+
+    Ok(advisories)
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdvisoryMatch {
+    pub installed_hash: String,
+    pub release: Entity<ReleaseEntry>,
+    pub advisory: Entity<AdvisoryEntry>,
+}
+
+/// Find the advisories that affect any of the installed hApp, UI or DNA hashes
+pub fn check(installed_hashes: Vec<String>) -> AppResult<Vec<AdvisoryMatch>> {
+    debug!("Checking advisories for {} hashes", installed_hashes.len());
+    let mut advisories_by_app: BTreeMap<EntityId, Vec<Entity<AdvisoryEntry>>> = BTreeMap::new();
+    let advisories: Vec<Entity<AdvisoryEntry>> =
+        get_entities(&advisories_path(), LinkTypes::Advisory, None)?;
+
+    for advisory in advisories {
+        advisories_by_app
+            .entry(advisory.content.app.to_owned())
+            .or_default()
+            .push(advisory);
+    }
+
+    let mut matches = vec![];
+    for (app, advisories) in advisories_by_app {
+        for release in release::get_all_for_app(&app)? {
+            let hashes = match ReleaseHashes::parse(&release.content.hashes) {
+                Ok(hashes) => hashes,
+                Err(_) => continue,
+            };
+            let installed_hash = match installed_hashes.iter().find(|hash| hashes.contains(hash)) {
+                Some(hash) => hash,
+                None => continue,
+            };
+
+            for advisory in advisories.iter() {
+                if advisory.content.affects(&release.content.version) {
+                    matches.push(AdvisoryMatch {
+                        installed_hash: installed_hash.to_owned(),
+                        release: release.clone(),
+                        advisory: advisory.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(matches)
+}
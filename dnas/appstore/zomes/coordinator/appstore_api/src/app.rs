@@ -1,16 +1,17 @@
-use crate::{AppResult, ANCHOR_AGENTS, ANCHOR_APPS, ANCHOR_PUBLISHERS};
+use crate::{hash_index, AppResult, UserError, ANCHOR_AGENTS, ANCHOR_APPS, ANCHOR_PUBLISHERS};
 use appstore::{
-    AppEntry, DeprecationNotice, EntityId, GetEntityInput, LinkTypes, UpdateEntityInput,
+    AppEntry, DeprecationNotice, EntityId, GetEntityInput, LinkTypes, LocalizedText,
+    ReleaseChannel, Screenshot, UpdateEntityInput,
 };
-use hc_crud::{create_entity, get_entity, now, update_entity, Entity};
+use hc_crud::{create_entity, get_entities, get_entity, now, update_entity, Entity};
 use hdk::prelude::*;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateInput {
     pub title: String,
     pub subtitle: String,
     pub description: String,
-    pub icon_src: String,
     pub publisher: EntityId,
     pub source: String,
     pub hashes: String,
@@ -18,6 +19,12 @@ pub struct CreateInput {
     pub metadata: Option<String>,
 
     // optional
+    pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub screenshots: Option<Vec<Screenshot>>,
+    pub default_locale: Option<String>,
+    pub localizations: Option<BTreeMap<String, LocalizedText>>,
+    pub channels: Option<Vec<ReleaseChannel>>,
     pub editors: Option<Vec<AgentPubKey>>,
 
     pub published_at: Option<u64>,
@@ -40,7 +47,7 @@ pub fn create(mut input: CreateInput) -> AppResult<Entity<AppEntry>> {
         title: input.title,
         subtitle: input.subtitle,
         description: input.description,
-        icon_src: input.icon_src,
+        icon_src: input.icon_src.unwrap_or_default(),
         publisher: input.publisher.clone(),
         source: input.source,
         hashes: input.hashes,
@@ -54,7 +61,18 @@ pub fn create(mut input: CreateInput) -> AppResult<Entity<AppEntry>> {
         last_updated: input.last_updated.unwrap_or(default_now),
 
         deprecation: None,
+        icon: input.icon,
+        screenshots: input.screenshots.unwrap_or_default(),
+        default_locale: input.default_locale,
+        localizations: input.localizations.unwrap_or_default(),
+        channels: input.channels.unwrap_or_default(),
     };
+
+    // Reject an invalid app before anything is written; scheduled draft publications run unattended
+    if let ValidateCallbackResult::Invalid(message) = appstore::validate_app_fields(&app)? {
+        Err(UserError::CustomError(message))?;
+    }
+
     let entity = create_entity(&app)?;
 
     {
@@ -84,10 +102,20 @@ pub fn create(mut input: CreateInput) -> AppResult<Entity<AppEntry>> {
         let (_, pathhash) = hc_utils::path_base(ANCHOR_APPS);
         entity.link_from(&pathhash, LinkTypes::App, None)?;
     }
+    hash_index::index_app(&entity, None)?;
 
     Ok(entity)
 }
 
+pub fn get_for_agent(agent: &AgentPubKey) -> AppResult<Vec<Entity<AppEntry>>> {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_AGENTS,
+        vec![agent.to_string(), ANCHOR_APPS.to_string()],
+    );
+
+    Ok(get_entities(&pathhash, LinkTypes::App, None)?)
+}
+
 pub fn get(input: GetEntityInput) -> AppResult<Entity<AppEntry>> {
     debug!("Get app: {}", input.id);
     let entity: Entity<AppEntry> = get_entity(&input.id)?;
@@ -95,12 +123,57 @@ pub fn get(input: GetEntityInput) -> AppResult<Entity<AppEntry>> {
     Ok(entity)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GetLocalizedInput {
+    pub id: EntityId,
+    pub locales: Vec<String>, // in order of preference
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalizedApp {
+    pub app: Entity<AppEntry>,
+    pub locale: Option<String>, // The locale the text was resolved to; None when nothing matched
+}
+
+pub fn get_localized(input: GetLocalizedInput) -> AppResult<LocalizedApp> {
+    debug!("Get app: {} for locales: {:?}", input.id, input.locales);
+    let mut entity: Entity<AppEntry> = get_entity(&input.id)?;
+    let locale = entity.content.resolve_locale(&input.locales);
+    entity.content = entity.content.localize(&input.locales);
+
+    Ok(LocalizedApp {
+        app: entity,
+        locale,
+    })
+}
+
+/// Resolve the text of each app for the preferred locales, leaving them untouched when none are given
+pub fn localize_all(
+    entities: Vec<Entity<AppEntry>>,
+    locales: &Option<Vec<String>>,
+) -> Vec<Entity<AppEntry>> {
+    match locales {
+        Some(locales) => entities
+            .into_iter()
+            .map(|mut entity| {
+                entity.content = entity.content.localize(locales);
+                entity
+            })
+            .collect(),
+        None => entities,
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct UpdateProperties {
     pub title: Option<String>,
     pub subtitle: Option<String>,
     pub description: Option<String>,
     pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub default_locale: Option<String>,
+    pub localizations: Option<BTreeMap<String, LocalizedText>>,
+    pub channels: Option<Vec<ReleaseChannel>>,
     pub source: Option<String>,
     pub hashes: Option<String>,
     pub metadata: Option<String>,
@@ -124,14 +197,23 @@ pub fn update(input: UpdateInput) -> AppResult<Entity<AppEntry>> {
         current.source = props.source.unwrap_or(current.source);
         current.hashes = props.hashes.unwrap_or(current.hashes);
         current.metadata = props.metadata;
-        current.icon_src = props.icon_src.unwrap_or(current.icon_src);
+        if let Some(icon) = props.icon {
+            current.icon = Some(icon);
+            current.icon_src = String::new();
+        } else if let Some(icon_src) = props.icon_src {
+            current.icon = None;
+            current.icon_src = icon_src;
+        }
+        current.default_locale = props.default_locale.or(current.default_locale);
+        current.localizations = props.localizations.unwrap_or(current.localizations);
+        current.channels = props.channels.unwrap_or(current.channels);
         current.published_at = props.published_at.unwrap_or(current.published_at);
         current.last_updated = props.last_updated.unwrap_or(current.last_updated);
 
         Ok(current)
     })?;
 
-    // let previous = previous.unwrap();
+    hash_index::index_app(&entity, previous.as_ref())?;
 
     Ok(entity)
 }
@@ -155,3 +237,25 @@ pub fn deprecate(input: DeprecateInput) -> AppResult<Entity<AppEntry>> {
 
     Ok(entity)
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SetScreenshotsInput {
+    pub base: ActionHash,
+    pub screenshots: Vec<Screenshot>,
+}
+
+/// Replaces the ordered screenshot list without touching any other field
+pub fn set_screenshots(input: SetScreenshotsInput) -> AppResult<Entity<AppEntry>> {
+    debug!(
+        "Setting {} screenshots for hApp: {}",
+        input.screenshots.len(),
+        input.base
+    );
+    let entity = update_entity(&input.base, |mut current: AppEntry, _| {
+        current.screenshots = input.screenshots.to_owned();
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
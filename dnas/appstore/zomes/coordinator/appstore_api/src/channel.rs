@@ -0,0 +1,98 @@
+use crate::{AppResult, EntryTypes};
+use appstore::{AppEntry, ChannelPreferenceEntry, EntityId, EntryTypesUnit, ReleaseChannel};
+use hc_crud::{get_entity, now, Entity};
+use hdk::prelude::*;
+
+/// Get the live channel preferences on this agent's source chain along with their action hashes
+///
+/// Channel preferences are private entries so this only reads the local source chain.
+fn query_preferences() -> AppResult<Vec<(ActionHash, ChannelPreferenceEntry)>> {
+    let deleted: Vec<ActionHash> = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let mut preferences = vec![];
+    for record in query(
+        ChainQueryFilter::new()
+            .entry_type(EntryTypesUnit::ChannelPreference.try_into()?)
+            .action_type(ActionType::Create)
+            .include_entries(true),
+    )? {
+        if deleted.contains(record.action_address()) {
+            continue;
+        }
+
+        if let Some(preference) = record.entry().to_app_option::<ChannelPreferenceEntry>()? {
+            preferences.push((record.action_address().to_owned(), preference));
+        }
+    }
+
+    Ok(preferences)
+}
+
+/// Follow the given channel for an app; following `Stable` removes the preference
+pub fn set(app: EntityId, channel: ReleaseChannel) -> AppResult<ChannelPreferenceEntry> {
+    debug!("Following {:?} channel for App: {}", channel, app);
+    let existing: Vec<(ActionHash, ChannelPreferenceEntry)> = query_preferences()?
+        .into_iter()
+        .filter(|(_, preference)| preference.app == app)
+        .collect();
+
+    if let [(_, preference)] = existing.as_slice() {
+        if preference.channel == channel {
+            return Ok(preference.to_owned());
+        }
+    }
+
+    // Make sure the app exists before following one of its channels
+    let _: Entity<AppEntry> = get_entity(&app)?;
+
+    for (action_hash, _) in existing {
+        delete_entry(action_hash)?;
+    }
+
+    let preference = ChannelPreferenceEntry {
+        app,
+        channel,
+        updated_at: now()?,
+    };
+
+    if channel != ReleaseChannel::default() {
+        create_entry(EntryTypes::ChannelPreference(preference.clone()))?;
+    }
+
+    Ok(preference)
+}
+
+/// Get the channel this agent follows for an app, `Stable` unless it opted into another
+pub fn get(app: &EntityId) -> AppResult<ReleaseChannel> {
+    Ok(query_preferences()?
+        .into_iter()
+        .filter(|(_, preference)| &preference.app == app)
+        .max_by_key(|(_, preference)| preference.updated_at)
+        .map(|(_, preference)| preference.channel)
+        .unwrap_or_default())
+}
+
+/// Get this agent's channel preferences, most recently changed first
+pub fn get_mine() -> AppResult<Vec<ChannelPreferenceEntry>> {
+    let mut preferences: Vec<ChannelPreferenceEntry> = query_preferences()?
+        .into_iter()
+        .map(|(_, preference)| preference)
+        .collect();
+    preferences.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+    Ok(preferences)
+}
+
+/// Resolve the channel to use for a lookup; an explicit channel wins over the stored preference
+pub fn resolve(app: &EntityId, channel: &Option<ReleaseChannel>) -> AppResult<ReleaseChannel> {
+    match channel {
+        Some(channel) => Ok(*channel),
+        None => get(app),
+    }
+}
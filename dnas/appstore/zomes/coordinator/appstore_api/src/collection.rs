@@ -0,0 +1,137 @@
+use crate::{delisting, AppResult, ANCHOR_COLLECTIONS};
+use appstore::{AppEntry, CollectionEntry, EntityId, GetEntityInput, LinkTypes, UpdateEntityInput};
+use hc_crud::{create_entity, get_entities, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub title: String,
+    pub description: String,
+    pub apps: Vec<EntityId>,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<CollectionEntry>> {
+    debug!("Creating Collection: {}", input.title);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+
+    let collection = CollectionEntry {
+        title: input.title,
+        description: input.description,
+        apps: input.apps,
+        official: appstore::is_steward(&pubkey)?,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&collection)?;
+
+    {
+        // Path via All Collections
+        let (_, pathhash) = hc_utils::path_base(ANCHOR_COLLECTIONS);
+        entity.link_from(&pathhash, LinkTypes::Collection, None)?;
+    }
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<CollectionEntry>> {
+    debug!("Get collection: {}", input.id);
+    let entity: Entity<CollectionEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub apps: Option<Vec<EntityId>>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<CollectionEntry>> {
+    debug!("Updating Collection: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+
+    let entity = update_entity(&input.base, |mut current: CollectionEntry, _| {
+        current.title = props.title.unwrap_or(current.title);
+        current.description = props.description.unwrap_or(current.description);
+        current.apps = props.apps.unwrap_or(current.apps);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Collection: {}", input.id);
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    let (_, pathhash) = hc_utils::path_base(ANCHOR_COLLECTIONS);
+    hc_utils::delete_links_to(&pathhash, LinkTypes::Collection, &input.id)?;
+
+    Ok(delete_hash)
+}
+
+/// Gets all collections with the official ones first
+pub fn get_all() -> AppResult<Vec<Entity<CollectionEntry>>> {
+    let (_, pathhash) = hc_utils::path_base(ANCHOR_COLLECTIONS);
+    let mut collections: Vec<Entity<CollectionEntry>> =
+        get_entities(&pathhash, LinkTypes::Collection, None)?;
+
+    collections.sort_by_key(|entity| !entity.content.official);
+
+    Ok(collections)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAppsInput {
+    pub id: EntityId,
+
+    // optional
+    pub locales: Option<Vec<String>>, // in order of preference
+}
+
+/// Gets the listed apps of a collection in the collection's order
+///
+/// The collection entry's own app list is the only source, so only its owner decides what is in it.
+/// Deprecated and delisted apps and ids that no longer resolve are skipped.
+pub fn get_apps(collection: &EntityId) -> AppResult<Vec<Entity<AppEntry>>> {
+    let entity: Entity<CollectionEntry> = get_entity(collection)?;
+    let delisted = delisting::get_delisted_ids()?;
+    let mut apps = vec![];
+
+    for app in entity.content.apps.iter() {
+        if delisted.apps.contains(app) {
+            continue;
+        }
+
+        // A missing or deleted app should not hide the rest of the collection
+        let app: Entity<AppEntry> = match get_entity(app) {
+            Ok(entity) => entity,
+            Err(error) => {
+                debug!(
+                    "Skipping unresolvable app {} in collection: {:?}",
+                    app, error
+                );
+                continue;
+            }
+        };
+        if app.content.deprecation.is_none() {
+            apps.push(app);
+        }
+    }
+
+    Ok(apps)
+}
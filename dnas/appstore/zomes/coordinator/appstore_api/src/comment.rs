@@ -0,0 +1,293 @@
+use crate::{AppResult, UserError, ANCHOR_APPS, ANCHOR_COMMENTS, ANCHOR_REPLIES};
+use appstore::{
+    AppEntry, CommentEntry, EntityId, GetEntityInput, LinkTypes, PublisherEntry, UpdateEntityInput,
+};
+use hc_crud::{create_entity, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const THREAD_BUCKET_MICROS: i64 = 30 * 24 * 60 * 60 * 1_000_000; // Comments are paged 30 days at a time
+
+/// The thread bucket of a point in time; comment links are tagged with the bucket they were made in
+fn thread_bucket(timestamp: &Timestamp) -> i64 {
+    timestamp.as_micros().div_euclid(THREAD_BUCKET_MICROS)
+}
+
+fn thread_bucket_tag(bucket: i64) -> LinkTag {
+    LinkTag::new(bucket.to_be_bytes().to_vec())
+}
+
+fn app_comments_path(app: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_COMMENTS.to_string()],
+    );
+
+    pathhash
+}
+
+fn comment_replies_path(comment: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_COMMENTS,
+        vec![comment.to_string(), ANCHOR_REPLIES.to_string()],
+    );
+
+    pathhash
+}
+
+/// Top-level comments are linked from the app and replies from their parent comment
+fn thread_path(app: &EntityId, parent: &Option<EntityId>) -> EntryHash {
+    match parent {
+        Some(parent) => comment_replies_path(parent),
+        None => app_comments_path(app),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub app: EntityId,
+    pub text: String,
+
+    // optional
+    pub parent: Option<EntityId>,
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<CommentEntry>> {
+    debug!("Creating Comment for App: {}", input.app);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+
+    let comment = CommentEntry {
+        app: input.app.clone(),
+        parent: input.parent.clone(),
+        text: input.text,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&comment)?;
+
+    create_link(
+        thread_path(&input.app, &input.parent),
+        entity.id.clone(),
+        LinkTypes::Comment,
+        thread_bucket_tag(thread_bucket(&sys_time()?)),
+    )?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<CommentEntry>> {
+    debug!("Get comment: {}", input.id);
+    let entity: Entity<CommentEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub text: Option<String>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<CommentEntry>> {
+    debug!("Updating Comment: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+
+    let entity = update_entity(&input.base, |mut current: CommentEntry, _| {
+        current.text = props.text.unwrap_or(current.text);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+/// Delete a comment, keeping its place in the thread while it has replies
+///
+/// Replies belong to their own authors, so they are left alone; the thread shows a placeholder for
+/// the deleted comment so they stay reachable.
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Comment: {}", input.id);
+    let entity: Entity<CommentEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    if get_links(comment_replies_path(&input.id), LinkTypes::Comment, None)?.is_empty() {
+        hc_utils::delete_links_to(
+            &thread_path(&entity.content.app, &entity.content.parent),
+            LinkTypes::Comment,
+            &input.id,
+        )?;
+    }
+
+    Ok(delete_hash)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentVersion {
+    pub action: ActionHash,
+    pub text: String,
+    pub edited_at: u64,
+}
+
+/// Get every version of a comment, oldest first, by following its update chain
+pub fn get_history(id: &EntityId) -> AppResult<Vec<CommentVersion>> {
+    debug!("Get history for Comment: {}", id);
+    let mut history = vec![];
+    let mut next = Some(id.to_owned());
+
+    while let Some(action) = next.take() {
+        let details = match get_details(action.clone(), GetOptions::default())? {
+            Some(Details::Record(details)) => details,
+            _ => Err(UserError::CustomError(format!(
+                "Comment version not found for action: {}",
+                action
+            )))?,
+        };
+        let comment: CommentEntry =
+            details
+                .record
+                .entry()
+                .to_app_option()?
+                .ok_or(UserError::CustomError(format!(
+                    "Action {} is not a comment",
+                    action
+                )))?;
+
+        history.push(CommentVersion {
+            action,
+            text: comment.text,
+            edited_at: comment.last_updated,
+        });
+
+        next = details
+            .updates
+            .iter()
+            .max_by_key(|update| update.action().timestamp())
+            .map(|update| update.action_address().to_owned());
+    }
+
+    Ok(history)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreadComment {
+    pub id: EntityId,
+    pub comment: Option<Entity<CommentEntry>>, // None for a deleted comment that still has replies
+    pub by_publisher: bool,                    // The author is an editor of the app's publisher
+    pub edited: bool,
+    pub reply_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetThreadInput {
+    pub app: EntityId,
+
+    // optional
+    pub parent: Option<EntityId>, // defaults to the app's top-level comments
+    pub from: Option<i64>, // `next` of the previous page; defaults to the start of the thread
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreadPage {
+    pub comments: Vec<ThreadComment>,
+    pub next: Option<i64>, // None once the page reaches the present
+}
+
+/// Get one page of a thread, oldest comment first
+///
+/// Comment links are tagged with the bucket of time they were made in, and pages are read one
+/// bucket at a time from the DHT, so only the links and comments of the requested page are
+/// fetched.  A page ends at a bucket boundary and so may hold more than `limit` comments.  Deleted
+/// comments are replaced by a placeholder while they have replies and left out otherwise.
+pub fn get_thread(input: GetThreadInput) -> AppResult<ThreadPage> {
+    debug!(
+        "Get thread for App: {} (parent: {:?})",
+        input.app, input.parent
+    );
+    let app: Entity<AppEntry> = get_entity(&input.app)?;
+    let publisher: Entity<PublisherEntry> = get_entity(&app.content.publisher)?;
+    let path = thread_path(&input.app, &input.parent);
+
+    let mut bucket = match input.from {
+        Some(bucket) => bucket,
+        None => {
+            let thread_start = input.parent.as_ref().unwrap_or(&input.app);
+            thread_bucket(
+                &must_get_action(thread_start.to_owned())?
+                    .action()
+                    .timestamp(),
+            )
+        }
+    };
+    let last_bucket = thread_bucket(&sys_time()?);
+    let limit = input.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let mut page = vec![];
+
+    while bucket <= last_bucket && page.len() < limit {
+        let mut links = get_links(
+            path.clone(),
+            LinkTypes::Comment,
+            Some(thread_bucket_tag(bucket)),
+        )?;
+        links.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.create_link_hash.cmp(&b.create_link_hash))
+        });
+        bucket += 1;
+
+        for link in links {
+            let id = match link.target.into_action_hash() {
+                Some(id) => id,
+                None => continue,
+            };
+            if page.iter().any(|comment: &ThreadComment| comment.id == id) {
+                continue;
+            }
+            let reply_count = get_links(comment_replies_path(&id), LinkTypes::Comment, None)?.len();
+
+            let comment: Entity<CommentEntry> = match get_entity(&id) {
+                Ok(comment) => comment,
+                Err(error) => {
+                    debug!("Unresolvable comment {}: {:?}", id, error);
+                    if reply_count > 0 {
+                        page.push(ThreadComment {
+                            id,
+                            comment: None,
+                            by_publisher: false,
+                            edited: false,
+                            reply_count,
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            let author = &comment.content.author;
+            let by_publisher =
+                &publisher.content.author == author || publisher.content.editors.contains(author);
+
+            page.push(ThreadComment {
+                id,
+                edited: comment.action != comment.id,
+                comment: Some(comment),
+                by_publisher,
+                reply_count,
+            });
+        }
+    }
+
+    Ok(ThreadPage {
+        comments: page,
+        next: Some(bucket).filter(|next| *next <= last_bucket),
+    })
+}
@@ -7,3 +7,18 @@ pub const VALUE_MD : &'static str = "value";
 pub const ANCHOR_AGENTS : &'static str = "agents";
 pub const ANCHOR_PUBLISHERS : &'static str = "publishers";
 pub const ANCHOR_APPS : &'static str = "apps";
+pub const ANCHOR_REVIEWS : &'static str = "reviews";
+pub const ANCHOR_RESPONSES : &'static str = "responses";
+pub const ANCHOR_MODERATION : &'static str = "moderation";
+pub const ANCHOR_REPORTS : &'static str = "reports";
+pub const ANCHOR_RELEASES : &'static str = "releases";
+pub const ANCHOR_DELISTINGS : &'static str = "delistings";
+pub const ANCHOR_COLLECTIONS : &'static str = "collections";
+pub const ANCHOR_ENDORSEMENTS : &'static str = "endorsements";
+pub const ANCHOR_VERIFICATIONS : &'static str = "verifications";
+pub const ANCHOR_INSTALLS : &'static str = "installs";
+pub const ANCHOR_COMMENTS : &'static str = "comments";
+pub const ANCHOR_REPLIES : &'static str = "replies";
+pub const ANCHOR_ADVISORIES : &'static str = "advisories";
+pub const ANCHOR_HASHES : &'static str = "hashes";
+pub const ANCHOR_DNAS : &'static str = "dnas";
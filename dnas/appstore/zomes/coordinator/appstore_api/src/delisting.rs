@@ -0,0 +1,146 @@
+use crate::{AppResult, ANCHOR_APPS, ANCHOR_DELISTINGS, ANCHOR_MODERATION};
+use appstore::{
+    DelistingEntry, DelistingTarget, EntityId, GetEntityInput, LinkTypes, ReleaseEntry,
+    UpdateEntityInput,
+};
+use hc_crud::{create_entity, get_entities, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub target: DelistingTarget,
+    pub reason: String,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+fn delistings_path() -> EntryHash {
+    let (_, pathhash) = hc_utils::path(ANCHOR_MODERATION, vec![ANCHOR_DELISTINGS]);
+
+    pathhash
+}
+
+fn app_delistings_path(app: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_DELISTINGS.to_string()],
+    );
+
+    pathhash
+}
+
+/// The app that a delisting applies to, which for a release is the release's app
+fn target_app(target: &DelistingTarget) -> AppResult<EntityId> {
+    Ok(match target {
+        DelistingTarget::App(id) => id.to_owned(),
+        DelistingTarget::Release(id) => {
+            let release: Entity<ReleaseEntry> = get_entity(id)?;
+            release.content.app
+        }
+    })
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<DelistingEntry>> {
+    debug!("Creating Delisting for: {:?}", input.target);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let app = target_app(&input.target)?;
+
+    let delisting = DelistingEntry {
+        target: input.target,
+        reason: input.reason,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&delisting)?;
+
+    // Path via All Delistings
+    entity.link_from(&delistings_path(), LinkTypes::Delisting, None)?;
+    // Path via App's Delistings
+    entity.link_from(&app_delistings_path(&app), LinkTypes::Delisting, None)?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<DelistingEntry>> {
+    debug!("Get delisting: {}", input.id);
+    let entity: Entity<DelistingEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub reason: Option<String>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<DelistingEntry>> {
+    debug!("Updating Delisting: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+
+    let entity = update_entity(&input.base, |mut current: DelistingEntry, _| {
+        current.reason = props.reason.unwrap_or(current.reason);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+/// Revokes a delisting so the target is listed again
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Revoking Delisting: {}", input.id);
+    let entity: Entity<DelistingEntry> = get_entity(&input.id)?;
+    let app = target_app(&entity.content.target)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    hc_utils::delete_links_to(&delistings_path(), LinkTypes::Delisting, &input.id)?;
+    hc_utils::delete_links_to(&app_delistings_path(&app), LinkTypes::Delisting, &input.id)?;
+
+    Ok(delete_hash)
+}
+
+pub fn get_all() -> AppResult<Vec<Entity<DelistingEntry>>> {
+    Ok(get_entities(
+        &delistings_path(),
+        LinkTypes::Delisting,
+        None,
+    )?)
+}
+
+/// Gets the delistings of an app and of any of its releases
+pub fn get_for_app(app: &EntityId) -> AppResult<Vec<Entity<DelistingEntry>>> {
+    Ok(get_entities(
+        &app_delistings_path(app),
+        LinkTypes::Delisting,
+        None,
+    )?)
+}
+
+#[derive(Debug, Default)]
+pub struct DelistedIds {
+    pub apps: Vec<EntityId>,
+    pub releases: Vec<EntityId>,
+}
+
+pub fn get_delisted_ids() -> AppResult<DelistedIds> {
+    let mut delisted = DelistedIds::default();
+
+    for entity in get_all()? {
+        match entity.content.target {
+            DelistingTarget::App(id) => delisted.apps.push(id),
+            DelistingTarget::Release(id) => delisted.releases.push(id),
+        }
+    }
+
+    Ok(delisted)
+}
@@ -0,0 +1,251 @@
+use crate::{app, AppResult, EntryTypes, UserError};
+use appstore::{
+    AppDraftEntry, AppEntry, EntityId, EntryTypesUnit, LocalizedText, ReleaseChannel, Screenshot,
+};
+use hc_crud::{now, Entity};
+use hdk::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+pub struct Draft {
+    pub id: ActionHash,     // Action hash of the draft's first version
+    pub action: ActionHash, // Action hash of the latest version
+    pub content: AppDraftEntry,
+}
+
+/// Get the latest version of each live draft on this agent's source chain
+///
+/// Drafts are private entries so this only reads the local source chain.
+fn query_drafts() -> AppResult<Vec<Draft>> {
+    let deleted: Vec<ActionHash> = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    // Map each version's action hash to the draft id while walking the chain in order
+    let mut draft_ids: BTreeMap<ActionHash, ActionHash> = BTreeMap::new();
+    let mut drafts: BTreeMap<ActionHash, Draft> = BTreeMap::new();
+
+    for record in query(
+        ChainQueryFilter::new()
+            .entry_type(EntryTypesUnit::AppDraft.try_into()?)
+            .include_entries(true),
+    )? {
+        let action = record.action_address().to_owned();
+        let id = match record.action() {
+            Action::Create(_) => action.clone(),
+            Action::Update(update) => match draft_ids.get(&update.original_action_address) {
+                Some(id) => id.to_owned(),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        if let Some(content) = record.entry().to_app_option::<AppDraftEntry>()? {
+            draft_ids.insert(action.clone(), id.clone());
+            drafts.insert(
+                id.clone(),
+                Draft {
+                    id,
+                    action,
+                    content,
+                },
+            );
+        }
+    }
+
+    Ok(drafts
+        .into_values()
+        .filter(|draft| !deleted.contains(&draft.action))
+        .collect())
+}
+
+fn get_draft(id: &ActionHash) -> AppResult<Draft> {
+    Ok(query_drafts()?
+        .into_iter()
+        .find(|draft| &draft.id == id)
+        .ok_or(UserError::CustomError(format!(
+            "Draft not found for id: {}",
+            id
+        )))?)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub title: String,
+
+    // optional
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub publisher: Option<EntityId>,
+    pub source: Option<String>,
+    pub hashes: Option<String>,
+    pub changelog: Option<String>,
+    pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub screenshots: Option<Vec<Screenshot>>,
+    pub default_locale: Option<String>,
+    pub localizations: Option<BTreeMap<String, LocalizedText>>,
+    pub channels: Option<Vec<ReleaseChannel>>,
+    pub editors: Option<Vec<AgentPubKey>>,
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Draft> {
+    debug!("Creating App draft: {}", input.title);
+    let default_now = now()?;
+
+    let draft = AppDraftEntry {
+        title: input.title,
+        subtitle: input.subtitle.unwrap_or_default(),
+        description: input.description.unwrap_or_default(),
+        publisher: input.publisher,
+        source: input.source.unwrap_or_default(),
+        hashes: input.hashes.unwrap_or_default(),
+        changelog: input.changelog,
+        icon_src: input.icon_src,
+        icon: input.icon,
+        screenshots: input.screenshots.unwrap_or_default(),
+        default_locale: input.default_locale,
+        localizations: input.localizations.unwrap_or_default(),
+        editors: input.editors,
+        metadata: input.metadata,
+        channels: input.channels.unwrap_or_default(),
+
+        created_at: default_now,
+        last_updated: default_now,
+    };
+    let action = create_entry(EntryTypes::AppDraft(draft.clone()))?;
+
+    Ok(Draft {
+        id: action.clone(),
+        action,
+        content: draft,
+    })
+}
+
+pub fn get(id: ActionHash) -> AppResult<Draft> {
+    debug!("Get draft: {}", id);
+    get_draft(&id)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub publisher: Option<EntityId>,
+    pub source: Option<String>,
+    pub hashes: Option<String>,
+    pub changelog: Option<String>,
+    pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
+    pub screenshots: Option<Vec<Screenshot>>,
+    pub default_locale: Option<String>,
+    pub localizations: Option<BTreeMap<String, LocalizedText>>,
+    pub channels: Option<Vec<ReleaseChannel>>,
+    pub editors: Option<Vec<AgentPubKey>>,
+    pub metadata: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInput {
+    pub base: ActionHash, // Draft id
+    pub properties: UpdateProperties,
+}
+
+pub fn update(input: UpdateInput) -> AppResult<Draft> {
+    debug!("Updating App draft: {}", input.base);
+    let props = input.properties;
+    let draft = get_draft(&input.base)?;
+    let mut current = draft.content;
+
+    current.title = props.title.unwrap_or(current.title);
+    current.subtitle = props.subtitle.unwrap_or(current.subtitle);
+    current.description = props.description.unwrap_or(current.description);
+    current.publisher = props.publisher.or(current.publisher);
+    current.source = props.source.unwrap_or(current.source);
+    current.hashes = props.hashes.unwrap_or(current.hashes);
+    current.changelog = props.changelog.or(current.changelog);
+    if let Some(icon) = props.icon {
+        current.icon = Some(icon);
+        current.icon_src = None;
+    } else if let Some(icon_src) = props.icon_src {
+        current.icon = None;
+        current.icon_src = Some(icon_src);
+    }
+    current.screenshots = props.screenshots.unwrap_or(current.screenshots);
+    current.default_locale = props.default_locale.or(current.default_locale);
+    current.localizations = props.localizations.unwrap_or(current.localizations);
+    current.channels = props.channels.unwrap_or(current.channels);
+    current.editors = props.editors.or(current.editors);
+    current.metadata = props.metadata.or(current.metadata);
+    current.last_updated = now()?;
+
+    let action = update_entry(draft.action, EntryTypes::AppDraft(current.clone()))?;
+
+    Ok(Draft {
+        id: draft.id,
+        action,
+        content: current,
+    })
+}
+
+pub fn delete(id: ActionHash) -> AppResult<ActionHash> {
+    debug!("Deleting App draft: {}", id);
+    let draft = get_draft(&id)?;
+
+    Ok(delete_entry(draft.action)?)
+}
+
+/// Create the public AppEntry (and its anchor links) from a draft and remove the draft
+///
+/// Everything is written in this one zome call so the source chain commits the app, its links and
+/// the draft deletion together or not at all.
+pub fn publish(id: ActionHash) -> AppResult<Entity<AppEntry>> {
+    debug!("Publishing App draft: {}", id);
+    let draft = get_draft(&id)?;
+    let content = draft.content;
+
+    let publisher = content.publisher.ok_or(UserError::CustomError(format!(
+        "Draft {} cannot be published without a publisher",
+        id
+    )))?;
+
+    let entity = app::create(app::CreateInput {
+        title: content.title,
+        subtitle: content.subtitle,
+        description: content.description,
+        publisher,
+        source: content.source,
+        hashes: content.hashes,
+        changelog: content.changelog,
+        metadata: content.metadata,
+
+        icon_src: content.icon_src,
+        icon: content.icon,
+        screenshots: Some(content.screenshots),
+        default_locale: content.default_locale,
+        localizations: Some(content.localizations),
+        channels: Some(content.channels),
+        editors: content.editors,
+
+        published_at: None,
+        last_updated: None,
+    })?;
+
+    delete_entry(draft.action)?;
+
+    Ok(entity)
+}
+
+/// Get this agent's drafts, most recently updated first
+pub fn get_mine() -> AppResult<Vec<Draft>> {
+    let mut drafts = query_drafts()?;
+    drafts.sort_by(|a, b| b.content.last_updated.cmp(&a.content.last_updated));
+
+    Ok(drafts)
+}
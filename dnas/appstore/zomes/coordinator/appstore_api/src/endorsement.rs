@@ -0,0 +1,224 @@
+use crate::{AppResult, ANCHOR_AGENTS, ANCHOR_ENDORSEMENTS, ANCHOR_PUBLISHERS};
+use appstore::{EndorsementEntry, EntityId, GetEntityInput, LinkTypes, PublisherEntry};
+use hc_crud::{create_entity, get_entity, now, Entity};
+use hdk::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+const TRUST_DAMPING: f64 = 0.5; // Trust passed on by an agent who was trusted by association
+const TRUST_MAX_DEPTH: u8 = 3;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub publisher: EntityId,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+fn publisher_endorsements_path(publisher: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_PUBLISHERS,
+        vec![publisher.to_string(), ANCHOR_ENDORSEMENTS.to_string()],
+    );
+
+    pathhash
+}
+
+fn agent_endorsements_path(agent: &AgentPubKey) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_AGENTS,
+        vec![agent.to_string(), ANCHOR_ENDORSEMENTS.to_string()],
+    );
+
+    pathhash
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<EndorsementEntry>> {
+    debug!("Endorsing Publisher: {}", input.publisher);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let signature = sign_raw(pubkey.clone(), input.publisher.get_raw_39().to_vec())?;
+
+    let endorsement = EndorsementEntry {
+        publisher: input.publisher.clone(),
+        signature,
+
+        author: pubkey.clone(),
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&endorsement)?;
+
+    // Path via Publisher's Endorsements
+    entity.link_from(
+        &publisher_endorsements_path(&input.publisher),
+        LinkTypes::Endorsement,
+        None,
+    )?;
+    // Path via Agent's Endorsements
+    entity.link_from(
+        &agent_endorsements_path(&pubkey),
+        LinkTypes::Endorsement,
+        None,
+    )?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<EndorsementEntry>> {
+    debug!("Get endorsement: {}", input.id);
+    let entity: Entity<EndorsementEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+pub fn revoke(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Revoking Endorsement: {}", input.id);
+    let entity: Entity<EndorsementEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    hc_utils::delete_links_to(
+        &publisher_endorsements_path(&entity.content.publisher),
+        LinkTypes::Endorsement,
+        &input.id,
+    )?;
+    hc_utils::delete_links_to(
+        &agent_endorsements_path(&entity.content.author),
+        LinkTypes::Endorsement,
+        &input.id,
+    )?;
+
+    Ok(delete_hash)
+}
+
+/// Get the endorsements linked from a path, skipping targets that no longer resolve so that one
+/// bad link cannot fail a listing or the trust score walk
+fn get_linked_endorsements(base: EntryHash) -> AppResult<Vec<Entity<EndorsementEntry>>> {
+    let mut endorsements = vec![];
+
+    for link in get_links(base, LinkTypes::Endorsement, None)? {
+        if let Some(id) = link.target.into_action_hash() {
+            match get_entity(&id) {
+                Ok(entity) => endorsements.push(entity),
+                Err(error) => debug!("Skipping unresolvable endorsement {}: {:?}", id, error),
+            }
+        }
+    }
+
+    Ok(endorsements)
+}
+
+/// Gets a publisher's endorsements, at most one per author
+pub fn get_for_publisher(publisher: &EntityId) -> AppResult<Vec<Entity<EndorsementEntry>>> {
+    let mut authors = BTreeSet::new();
+
+    Ok(
+        get_linked_endorsements(publisher_endorsements_path(publisher))?
+            .into_iter()
+            .filter(|entity| &entity.content.publisher == publisher)
+            .filter(|entity| authors.insert(entity.content.author.to_owned()))
+            .collect(),
+    )
+}
+
+/// Gets the endorsements made by an agent, at most one per publisher
+pub fn get_for_agent(agent: &AgentPubKey) -> AppResult<Vec<Entity<EndorsementEntry>>> {
+    let mut publishers = BTreeSet::new();
+
+    Ok(get_linked_endorsements(agent_endorsements_path(agent))?
+        .into_iter()
+        .filter(|entity| &entity.content.author == agent)
+        .filter(|entity| publishers.insert(entity.content.publisher.to_owned()))
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetTrustScoreInput {
+    pub publisher: EntityId,
+
+    // optional
+    pub trusted_agents: Option<Vec<AgentPubKey>>, // defaults to the stewards
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrustScore {
+    pub publisher: EntityId,
+    pub score: f64, // 0 (no trusted endorsements) to 1
+    pub endorsements: u64,
+}
+
+/// Compute a publisher's trust score by walking the endorsement graph out from the trusted agents
+///
+/// Trusted agents carry a trust of 1.  Each endorsement passes the endorser's trust to the
+/// publisher, and multiple endorsements combine as `1 - Π(1 - trust)`.  The editors of an endorsed
+/// publisher are then trusted at the publisher's score times `TRUST_DAMPING` for the next hop.  Only
+/// endorsements authored by the walked agent count, and each (author, publisher) pair counts once.
+pub fn get_trust_score(input: GetTrustScoreInput) -> AppResult<TrustScore> {
+    debug!("Get trust score for Publisher: {}", input.publisher);
+    let seeds = match input.trusted_agents {
+        Some(agents) => agents,
+        None => appstore::get_stewards()?,
+    };
+
+    let mut agent_trust: BTreeMap<AgentPubKey, f64> =
+        seeds.into_iter().map(|agent| (agent, 1.0)).collect();
+    let mut publisher_distrust: BTreeMap<EntityId, f64> = BTreeMap::new();
+    let mut frontier: Vec<AgentPubKey> = agent_trust.keys().cloned().collect();
+    let mut counted: BTreeSet<(AgentPubKey, EntityId)> = BTreeSet::new();
+
+    for _ in 0..TRUST_MAX_DEPTH {
+        let mut endorsed = vec![];
+
+        for agent in frontier.iter() {
+            let trust = agent_trust.get(agent).cloned().unwrap_or(0.0);
+
+            for endorsement in get_for_agent(agent)? {
+                let publisher = endorsement.content.publisher;
+                if !counted.insert((endorsement.content.author, publisher.clone())) {
+                    continue;
+                }
+                let distrust = publisher_distrust.entry(publisher.clone()).or_insert(1.0);
+                *distrust *= 1.0 - trust;
+
+                if !endorsed.contains(&publisher) {
+                    endorsed.push(publisher);
+                }
+            }
+        }
+
+        let mut next_frontier = vec![];
+        for publisher in endorsed.iter() {
+            let score = 1.0 - publisher_distrust.get(publisher).cloned().unwrap_or(1.0);
+            let entity: Entity<PublisherEntry> = match get_entity(publisher) {
+                Ok(entity) => entity,
+                Err(error) => {
+                    debug!("Skipping unresolvable publisher {}: {:?}", publisher, error);
+                    continue;
+                }
+            };
+
+            for editor in entity.content.editors.iter() {
+                if !agent_trust.contains_key(editor) {
+                    agent_trust.insert(editor.to_owned(), score * TRUST_DAMPING);
+                    next_frontier.push(editor.to_owned());
+                }
+            }
+        }
+
+        if next_frontier.is_empty() {
+            break;
+        }
+        frontier = next_frontier;
+    }
+
+    Ok(TrustScore {
+        score: 1.0
+            - publisher_distrust
+                .get(&input.publisher)
+                .cloned()
+                .unwrap_or(1.0),
+        endorsements: get_for_publisher(&input.publisher)?.len() as u64,
+        publisher: input.publisher,
+    })
+}
@@ -0,0 +1,88 @@
+use crate::{AppResult, EntryTypes, UserError};
+use appstore::{AppEntry, EntityId, EntryTypesUnit, FavoriteEntry};
+use hc_crud::{get_entity, now, Entity};
+use hdk::prelude::*;
+
+/// Get the live favorites on this agent's source chain along with their action hashes
+///
+/// Favorites are private entries so this only reads the local source chain.
+fn query_favorites() -> AppResult<Vec<(ActionHash, FavoriteEntry)>> {
+    let deleted: Vec<ActionHash> = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let mut favorites = vec![];
+    for record in query(
+        ChainQueryFilter::new()
+            .entry_type(EntryTypesUnit::Favorite.try_into()?)
+            .action_type(ActionType::Create)
+            .include_entries(true),
+    )? {
+        if deleted.contains(record.action_address()) {
+            continue;
+        }
+
+        if let Some(favorite) = record.entry().to_app_option::<FavoriteEntry>()? {
+            favorites.push((record.action_address().to_owned(), favorite));
+        }
+    }
+
+    Ok(favorites)
+}
+
+pub fn add(app: EntityId) -> AppResult<FavoriteEntry> {
+    debug!("Adding favorite App: {}", app);
+    if let Some((_, favorite)) = query_favorites()?
+        .into_iter()
+        .find(|(_, favorite)| favorite.app == app)
+    {
+        return Ok(favorite);
+    }
+
+    // Make sure the app exists before bookmarking it
+    let _: Entity<AppEntry> = get_entity(&app)?;
+
+    let favorite = FavoriteEntry {
+        app,
+        added_at: now()?,
+    };
+    create_entry(EntryTypes::Favorite(favorite.clone()))?;
+
+    Ok(favorite)
+}
+
+pub fn remove(app: EntityId) -> AppResult<Vec<ActionHash>> {
+    debug!("Removing favorite App: {}", app);
+    let mut deleted = vec![];
+
+    for (action_hash, _) in query_favorites()?
+        .into_iter()
+        .filter(|(_, favorite)| favorite.app == app)
+    {
+        deleted.push(delete_entry(action_hash)?);
+    }
+
+    if deleted.is_empty() {
+        Err(UserError::CustomError(format!(
+            "App {} is not one of your favorites",
+            app
+        )))?;
+    }
+
+    Ok(deleted)
+}
+
+/// Get this agent's favorites, most recently added first
+pub fn get_mine() -> AppResult<Vec<FavoriteEntry>> {
+    let mut favorites: Vec<FavoriteEntry> = query_favorites()?
+        .into_iter()
+        .map(|(_, favorite)| favorite)
+        .collect();
+    favorites.sort_by(|a, b| b.added_at.cmp(&a.added_at));
+
+    Ok(favorites)
+}
@@ -0,0 +1,374 @@
+use crate::{channel, delisting, release, AppResult, ANCHOR_DNAS, ANCHOR_HASHES};
+use appstore::{
+    parse_version, AppEntry, DeprecationNotice, EntityId, LinkTypes, ReleaseChannel, ReleaseEntry,
+    ReleaseHashes, RoleManifest,
+};
+use hc_crud::{get_entity, Entity};
+use hdk::prelude::*;
+
+// Reverse index from the webhapp and hApp bundle hashes in an app's or release's `hashes` JSON to
+// the entity that declared them, and from DNA hashes to the apps using them.  UI and DNA hashes are
+// shared by many apps, so they do not identify one.  Hashes that fail to parse are simply not
+// indexed.
+
+fn dna_path(dna: &str) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(ANCHOR_DNAS, vec![dna.to_string()]);
+
+    pathhash
+}
+
+fn hash_path(hash: &str) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(ANCHOR_HASHES, vec![ReleaseHashes::normalize(hash)]);
+
+    pathhash
+}
+
+fn parse_bundle_hashes(hashes: &str) -> Vec<String> {
+    ReleaseHashes::parse(hashes)
+        .map(|hashes| hashes.bundles().into_iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn index(id: &EntityId, hashes: &str, link_type: LinkTypes) -> AppResult<()> {
+    for hash in parse_bundle_hashes(hashes) {
+        create_link(hash_path(&hash), id.to_owned(), link_type, ())?;
+    }
+
+    Ok(())
+}
+
+fn unindex(id: &EntityId, hashes: &str, link_type: LinkTypes) -> AppResult<()> {
+    for hash in parse_bundle_hashes(hashes) {
+        hc_utils::delete_links_to(&hash_path(&hash), link_type, id)?;
+    }
+
+    Ok(())
+}
+
+/// Index `current` hashes, removing the links of `previous` hashes that are no longer declared
+fn reindex(
+    id: &EntityId,
+    previous: Option<&str>,
+    current: &str,
+    link_type: LinkTypes,
+) -> AppResult<()> {
+    if previous == Some(current) {
+        return Ok(());
+    }
+    if let Some(previous) = previous {
+        unindex(id, previous, link_type)?;
+    }
+
+    index(id, current, link_type)
+}
+
+/// Link the app from the anchor of each DNA declared in its hashes or release roles
+///
+/// Links are only added; an app stays listed under the DNAs that its earlier releases used.
+fn index_dnas(app: &EntityId, hashes: &str, roles: &Vec<RoleManifest>) -> AppResult<()> {
+    let mut dnas = ReleaseHashes::parse(hashes)
+        .map(|hashes| hashes.dnas)
+        .unwrap_or_default();
+    for role in roles.iter() {
+        let dna = role.dna.to_string();
+        if !dnas.contains(&dna) {
+            dnas.push(dna);
+        }
+    }
+    let target: AnyLinkableHash = app.to_owned().into();
+
+    for dna in dnas {
+        let already_linked = get_links(dna_path(&dna), LinkTypes::App, None)?
+            .into_iter()
+            .any(|link| link.target == target);
+
+        if !already_linked {
+            create_link(dna_path(&dna), app.to_owned(), LinkTypes::App, ())?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn index_app(entity: &Entity<AppEntry>, previous: Option<&AppEntry>) -> AppResult<()> {
+    index_dnas(&entity.id, &entity.content.hashes, &vec![])?;
+    reindex(
+        &entity.id,
+        previous.map(|app| app.hashes.as_str()),
+        &entity.content.hashes,
+        LinkTypes::App,
+    )
+}
+
+pub fn index_release(
+    entity: &Entity<ReleaseEntry>,
+    previous: Option<&ReleaseEntry>,
+) -> AppResult<()> {
+    index_dnas(
+        &entity.content.app,
+        &entity.content.hashes,
+        &entity.content.roles,
+    )?;
+    reindex(
+        &entity.id,
+        previous.map(|release| release.hashes.as_str()),
+        &entity.content.hashes,
+        LinkTypes::Release,
+    )
+}
+
+/// Hash links are not validated, so only trust one made by an editor of the app for an entity
+/// that really declares the hash
+fn is_declared_by(hash: &str, hashes: &str, app: &AppEntry, link_author: &AgentPubKey) -> bool {
+    app.editors.contains(link_author)
+        && ReleaseHashes::parse(hashes)
+            .map(|hashes| hashes.contains_bundle(hash))
+            .unwrap_or(false)
+}
+
+/// Get the hash's links of the given type, oldest first
+fn get_hash_links(hash: &str, link_type: LinkTypes) -> AppResult<Vec<Link>> {
+    let mut links = get_links(hash_path(hash), link_type, None)?;
+    links.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.create_link_hash.cmp(&b.create_link_hash))
+    });
+
+    Ok(links)
+}
+
+fn find_release(hash: &str) -> AppResult<Option<(Entity<AppEntry>, Entity<ReleaseEntry>)>> {
+    for link in get_hash_links(hash, LinkTypes::Release)? {
+        let release_id = match link.target.into_action_hash() {
+            Some(release_id) => release_id,
+            None => continue,
+        };
+        let release: Entity<ReleaseEntry> = match get_entity(&release_id) {
+            Ok(release) => release,
+            Err(error) => {
+                debug!("Skipping unresolvable release {}: {:?}", release_id, error);
+                continue;
+            }
+        };
+        let app: Entity<AppEntry> = match get_entity(&release.content.app) {
+            Ok(app) => app,
+            Err(error) => {
+                debug!(
+                    "Skipping release {} of unresolvable app: {:?}",
+                    release_id, error
+                );
+                continue;
+            }
+        };
+
+        if is_declared_by(hash, &release.content.hashes, &app.content, &link.author) {
+            return Ok(Some((app, release)));
+        }
+        debug!(
+            "Ignoring hash link to release {} that it does not declare",
+            release_id
+        );
+    }
+
+    Ok(None)
+}
+
+fn find_app(hash: &str) -> AppResult<Option<Entity<AppEntry>>> {
+    for link in get_hash_links(hash, LinkTypes::App)? {
+        let app_id = match link.target.into_action_hash() {
+            Some(app_id) => app_id,
+            None => continue,
+        };
+        let app: Entity<AppEntry> = match get_entity(&app_id) {
+            Ok(app) => app,
+            Err(error) => {
+                debug!("Skipping unresolvable app {}: {:?}", app_id, error);
+                continue;
+            }
+        };
+
+        if is_declared_by(hash, &app.content.hashes, &app.content, &link.author) {
+            return Ok(Some(app));
+        }
+        debug!(
+            "Ignoring hash link to app {} that it does not declare",
+            app_id
+        );
+    }
+
+    Ok(None)
+}
+
+/// Find the app, and release if there is one, that declared any of the given bundle hashes
+///
+/// Links whose target no longer resolves, or does not declare the hash, are skipped.
+pub fn lookup(
+    hashes: &Vec<String>,
+) -> AppResult<Option<(Entity<AppEntry>, Option<Entity<ReleaseEntry>>)>> {
+    for hash in hashes.iter() {
+        if let Some((app, release)) = find_release(hash)? {
+            return Ok(Some((app, Some(release))));
+        }
+    }
+
+    for hash in hashes.iter() {
+        if let Some(app) = find_app(hash)? {
+            return Ok(Some((app, None)));
+        }
+    }
+
+    Ok(None)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledHashes {
+    pub happ: Option<String>,
+    #[serde(default)]
+    pub webhapp: Option<String>,
+    pub channel: Option<ReleaseChannel>, // defaults to the channel this agent follows for the app
+}
+
+impl InstalledHashes {
+    fn bundles(&self) -> Vec<String> {
+        self.happ
+            .iter()
+            .chain(self.webhapp.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Whether `current` has a higher version than `installed`; None when either version is not semver
+fn is_newer(current: &ReleaseEntry, installed: &ReleaseEntry) -> Option<bool> {
+    match (
+        parse_version(&current.version),
+        parse_version(&installed.version),
+    ) {
+        (Ok(current), Ok(installed)) => Some(current > installed),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateCheck {
+    pub installed: InstalledHashes,
+    pub app: Option<EntityId>, // None when the store does not know any of the hashes
+    pub installed_release: Option<EntityId>,
+    pub channel: Option<ReleaseChannel>, // the channel the current release was looked up in
+    pub current_release: Option<Entity<ReleaseEntry>>,
+    pub update_available: Option<bool>, // None when the installed release or its version is not known
+    pub deprecation: Option<DeprecationNotice>,
+    pub delisted: bool,
+}
+
+pub fn check_for_updates(installed: Vec<InstalledHashes>) -> AppResult<Vec<UpdateCheck>> {
+    debug!("Checking {} installed apps for updates", installed.len());
+    let delisted = delisting::get_delisted_ids()?;
+    let mut checks = vec![];
+
+    for installed in installed {
+        let (app, installed_release) = match lookup(&installed.bundles())? {
+            Some(found) => found,
+            None => {
+                checks.push(UpdateCheck {
+                    installed,
+                    app: None,
+                    installed_release: None,
+                    channel: None,
+                    current_release: None,
+                    update_available: None,
+                    deprecation: None,
+                    delisted: false,
+                });
+                continue;
+            }
+        };
+        let app_id = app.id;
+        let channel = channel::resolve(&app_id, &installed.channel)?;
+        let current_release = release::get_latest(&app_id, &channel)?;
+
+        checks.push(UpdateCheck {
+            installed,
+            update_available: match (&current_release, &installed_release) {
+                (Some(current), Some(installed)) => is_newer(&current.content, &installed.content),
+                // Only the app is known, so it cannot be told whether the current release is newer
+                (Some(_), None) => None,
+                (None, _) => Some(false),
+            },
+            app: Some(app_id.to_owned()),
+            channel: Some(channel),
+            delisted: delisted.apps.contains(&app_id)
+                || installed_release
+                    .as_ref()
+                    .map_or(false, |release| delisted.releases.contains(&release.id)),
+            installed_release: installed_release.map(|release| release.id),
+            current_release,
+            deprecation: app.content.deprecation,
+        });
+    }
+
+    Ok(checks)
+}
+
+/// Whether the app or one of its releases declares the DNA, in its hashes or roles
+fn declares_dna(app: &Entity<AppEntry>, dna: &DnaHash) -> AppResult<bool> {
+    let dna_string = dna.to_string();
+    let has_dna = |hashes: &str| {
+        ReleaseHashes::parse(hashes)
+            .map(|hashes| hashes.dnas.contains(&dna_string))
+            .unwrap_or(false)
+    };
+
+    if has_dna(&app.content.hashes) {
+        return Ok(true);
+    }
+
+    Ok(release::get_all_for_app(&app.id)?
+        .into_iter()
+        .any(|release| {
+            has_dna(&release.content.hashes)
+                || release.content.roles.iter().any(|role| &role.dna == dna)
+        }))
+}
+
+/// Get the listed apps that declared the given DNA in their own or a release's hashes
+///
+/// DNA links are not validated, so only links made by one of the app's editors for an app that
+/// really declares the DNA are trusted; apps that no longer resolve are skipped.
+pub fn get_apps_using_dna(dna: &DnaHash) -> AppResult<Vec<Entity<AppEntry>>> {
+    debug!("Get apps using DNA: {}", dna);
+    let delisted = delisting::get_delisted_ids()?;
+    let mut apps: Vec<Entity<AppEntry>> = vec![];
+
+    for link in get_links(dna_path(&dna.to_string()), LinkTypes::App, None)? {
+        let app_id = match link.target.into_action_hash() {
+            Some(app_id) => app_id,
+            None => continue,
+        };
+        if delisted.apps.contains(&app_id) || apps.iter().any(|app| app.id == app_id) {
+            continue;
+        }
+
+        let app: Entity<AppEntry> = match get_entity(&app_id) {
+            Ok(app) => app,
+            Err(error) => {
+                debug!("Skipping unresolvable app {}: {:?}", app_id, error);
+                continue;
+            }
+        };
+        if !app.content.editors.contains(&link.author) || !declares_dna(&app, dna)? {
+            debug!(
+                "Ignoring DNA link to app {} that it does not declare",
+                app_id
+            );
+            continue;
+        }
+
+        if app.content.deprecation.is_none() {
+            apps.push(app);
+        }
+    }
+
+    Ok(apps)
+}
@@ -0,0 +1,115 @@
+use crate::{delisting, AppResult, UserError, ANCHOR_APPS, ANCHOR_INSTALLS};
+use appstore::{AppEntry, EntityId, LinkTypes};
+use hc_crud::{get_entity, Entity};
+use hdk::prelude::*;
+use std::collections::BTreeSet;
+
+fn app_installs_path(app: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_INSTALLS.to_string()],
+    );
+
+    pathhash
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordInput {
+    pub app: EntityId,
+
+    // optional
+    pub release: Option<EntityId>,
+}
+
+/// Record that this agent installed an app, which counts towards the app's popularity
+///
+/// Recording is opt-in; only agents that call this are counted, and each agent counts once per
+/// app.
+pub fn record(input: RecordInput) -> AppResult<u64> {
+    debug!("Recording install for App: {}", input.app);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let installs_path = app_installs_path(&input.app);
+    let agent_target: AnyLinkableHash = pubkey.clone().into();
+
+    let already_recorded = get_links(installs_path.clone(), LinkTypes::Install, None)?
+        .into_iter()
+        .any(|link| link.target == agent_target);
+    if already_recorded {
+        Err(UserError::CustomError(format!(
+            "Agent {} has already recorded an install for App {}",
+            pubkey, input.app
+        )))?;
+    }
+
+    let tag = match input.release {
+        Some(release) => release.get_raw_39().to_vec(),
+        None => vec![],
+    };
+    create_link(installs_path, pubkey, LinkTypes::Install, tag)?;
+
+    get_count(&input.app)
+}
+
+/// Count the distinct agents that recorded an install of an app
+///
+/// Validation allows one install link per agent per app, each targeting its author; counting
+/// distinct authors keeps the count honest even if a duplicate slipped in before it was rejected.
+pub fn get_count(app: &EntityId) -> AppResult<u64> {
+    let agents: BTreeSet<AgentPubKey> =
+        get_links(app_installs_path(app), LinkTypes::Install, None)?
+            .into_iter()
+            .map(|link| link.author)
+            .collect();
+
+    Ok(agents.len() as u64)
+}
+
+#[derive(Debug, Serialize)]
+pub struct PopularApp {
+    pub app: Entity<AppEntry>,
+    pub installs: u64,
+}
+
+/// Get the listed apps with the most recorded installs, most popular first
+///
+/// Installs are only recorded on each app's own path, so there is no shared counter for every
+/// installing agent to write to; the ranking counts each listed app's installs instead.
+pub fn get_popular(limit: usize) -> AppResult<Vec<PopularApp>> {
+    debug!("Get {} popular apps", limit);
+    let delisted = delisting::get_delisted_ids()?;
+    let (_, pathhash) = hc_utils::path_base(ANCHOR_APPS);
+    let mut apps = vec![];
+
+    for link in get_links(pathhash, LinkTypes::App, None)? {
+        let id = match link.target.into_action_hash() {
+            Some(id) => id,
+            None => continue,
+        };
+        if delisted.apps.contains(&id)
+            || apps.iter().any(|popular: &PopularApp| popular.app.id == id)
+        {
+            continue;
+        }
+
+        let app: Entity<AppEntry> = match get_entity(&id) {
+            Ok(entity) => entity,
+            Err(error) => {
+                debug!("Skipping unresolvable popular app {}: {:?}", id, error);
+                continue;
+            }
+        };
+        if app.content.deprecation.is_some() {
+            continue;
+        }
+
+        let installs = get_count(&app.id)?;
+        if installs > 0 {
+            apps.push(PopularApp { app, installs });
+        }
+    }
+
+    apps.sort_by(|a, b| b.installs.cmp(&a.installs));
+    apps.truncate(limit);
+
+    Ok(apps)
+}
@@ -1,13 +1,37 @@
+mod advisory;
 mod app;
+mod channel;
+mod collection;
+mod comment;
 mod constants;
+mod delisting;
+mod draft;
+mod endorsement;
+mod favorite;
+mod hash_index;
+mod install;
+mod memory;
 mod publisher;
+mod release;
+mod report;
+mod review;
+mod review_response;
+mod schedule;
+mod verification;
 
 pub use appstore::{
-    catch, composition, AppEntry, AppError, AppResult, Entity, EntityId, EntityResponse,
-    EntryTypes, GetEntityInput, LinkTypes, PublisherEntry, Response, UserError,
+    catch, composition, AdvisoryEntry, AppEntry, AppError, AppResult, ChannelPreferenceEntry,
+    CollectionEntry, CommentEntry, DelistingEntry, EndorsementEntry, Entity, EntityId,
+    EntityResponse, EntryTypes, FavoriteEntry, GetEntityInput, LinkTypes, MemoryEntry,
+    PublisherEntry, ReleaseChannel, ReleaseEntry, ReportEntry, Response, ReviewEntry,
+    ReviewResponseEntry, UserError, VerificationEntry,
 };
 pub use constants::{
-    ANCHOR_AGENTS, ANCHOR_APPS, ANCHOR_PUBLISHERS, ENTITY_COLLECTION_MD, ENTITY_MD, VALUE_MD,
+    ANCHOR_ADVISORIES, ANCHOR_AGENTS, ANCHOR_APPS, ANCHOR_COLLECTIONS, ANCHOR_COMMENTS,
+    ANCHOR_DELISTINGS, ANCHOR_DNAS, ANCHOR_ENDORSEMENTS, ANCHOR_HASHES, ANCHOR_INSTALLS,
+    ANCHOR_MODERATION, ANCHOR_PUBLISHERS, ANCHOR_RELEASES, ANCHOR_REPLIES, ANCHOR_REPORTS,
+    ANCHOR_RESPONSES, ANCHOR_REVIEWS, ANCHOR_VERIFICATIONS, ENTITY_COLLECTION_MD, ENTITY_MD,
+    VALUE_MD,
 };
 use hdk::prelude::*;
 pub use portal_types::{DnaZomeFunction, HostEntry};
@@ -17,6 +41,47 @@ pub struct GetForAgentInput {
     pub for_agent: AgentPubKey,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GetAppsInput {
+    // optional
+    pub locales: Option<Vec<String>>, // in order of preference
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetAppsForAgentInput {
+    pub for_agent: AgentPubKey,
+
+    // optional
+    pub locales: Option<Vec<String>>, // in order of preference
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetInstallableReleaseInput {
+    pub for_app: EntityId,
+    pub holochain_version: String,
+
+    // optional
+    pub channel: Option<ReleaseChannel>, // defaults to the channel this agent follows for the app
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetLatestReleaseInput {
+    pub for_app: EntityId,
+
+    // optional
+    pub channel: Option<ReleaseChannel>, // defaults to the channel this agent follows for the app
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDraftInput {
+    pub id: ActionHash,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetForAppInput {
+    pub for_app: EntityId,
+}
+
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     Ok(InitCallbackResult::Pass)
@@ -27,6 +92,12 @@ fn whoami(_: ()) -> ExternResult<Response<AgentInfo>> {
     Ok(composition(agent_info()?, VALUE_MD))
 }
 
+/// Gets the agents holding the steward role according to the DNA properties
+#[hdk_extern]
+fn get_stewards(_: ()) -> ExternResult<Response<Vec<AgentPubKey>>> {
+    Ok(composition(appstore::get_stewards()?, VALUE_MD))
+}
+
 // Publisher
 #[hdk_extern]
 fn create_publisher(input: publisher::CreateInput) -> ExternResult<EntityResponse<PublisherEntry>> {
@@ -36,10 +107,10 @@ fn create_publisher(input: publisher::CreateInput) -> ExternResult<EntityRespons
 }
 
 #[hdk_extern]
-fn get_publisher(input: GetEntityInput) -> ExternResult<EntityResponse<PublisherEntry>> {
-    let entity = catch!(publisher::get(input));
+fn get_publisher(input: GetEntityInput) -> ExternResult<Response<publisher::VerifiedPublisher>> {
+    let publisher = catch!(publisher::get_with_verification(input));
 
-    Ok(composition(entity, ENTITY_MD))
+    Ok(composition(publisher, VALUE_MD))
 }
 
 #[hdk_extern]
@@ -92,6 +163,104 @@ fn get_all_publishers(_: ()) -> ExternResult<Response<Vec<Entity<PublisherEntry>
     Ok(composition(collection, ENTITY_COLLECTION_MD))
 }
 
+// Verification
+/// Signs the challenge that the publisher must host on its website's domain
+#[hdk_extern]
+fn get_publisher_verification_challenge(
+    input: GetEntityInput,
+) -> ExternResult<Response<verification::VerificationChallenge>> {
+    let challenge = catch!(verification::create_challenge(input));
+
+    Ok(composition(challenge, VALUE_MD))
+}
+
+/// Records a steward's attestation that the publisher hosts a valid challenge on its domain
+#[hdk_extern]
+fn verify_publisher(
+    input: verification::CreateInput,
+) -> ExternResult<EntityResponse<VerificationEntry>> {
+    let entity = catch!(verification::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+/// Gets whether the publisher's current website domain is verified
+#[hdk_extern]
+fn get_publisher_verification(
+    input: GetEntityInput,
+) -> ExternResult<Response<verification::VerificationStatus>> {
+    let status = catch!(publisher::get(input).and_then(|entity| verification::get_status(&entity)));
+
+    Ok(composition(status, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_verification(input: GetEntityInput) -> ExternResult<EntityResponse<VerificationEntry>> {
+    let entity = catch!(verification::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn revoke_verification(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(verification::revoke(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_verifications_for_publisher(
+    input: GetEntityInput,
+) -> ExternResult<Response<Vec<Entity<VerificationEntry>>>> {
+    let collection = catch!(verification::get_for_publisher(&input.id));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+// Endorsement
+#[hdk_extern]
+fn endorse_publisher(
+    input: endorsement::CreateInput,
+) -> ExternResult<EntityResponse<EndorsementEntry>> {
+    let entity = catch!(endorsement::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_endorsement(input: GetEntityInput) -> ExternResult<EntityResponse<EndorsementEntry>> {
+    let entity = catch!(endorsement::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn revoke_endorsement(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(endorsement::revoke(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_endorsements_for_publisher(
+    input: GetEntityInput,
+) -> ExternResult<Response<Vec<Entity<EndorsementEntry>>>> {
+    let collection = catch!(endorsement::get_for_publisher(&input.id));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+/// Computes a publisher's trust score from the endorsement graph, seeded by the stewards unless
+/// other trusted agents are given
+#[hdk_extern]
+fn get_publisher_trust_score(
+    input: endorsement::GetTrustScoreInput,
+) -> ExternResult<Response<endorsement::TrustScore>> {
+    let score = catch!(endorsement::get_trust_score(input));
+
+    Ok(composition(score, VALUE_MD))
+}
+
 // App
 #[hdk_extern]
 fn create_app(input: app::CreateInput) -> ExternResult<EntityResponse<AppEntry>> {
@@ -108,6 +277,15 @@ fn get_app(input: GetEntityInput) -> ExternResult<EntityResponse<AppEntry>> {
     Ok(composition(entity, ENTITY_MD))
 }
 
+/// Gets the latest AppEntry with its text resolved for the preferred locales, and which locale
+/// that was
+#[hdk_extern]
+fn get_localized_app(input: app::GetLocalizedInput) -> ExternResult<Response<app::LocalizedApp>> {
+    let localized = catch!(app::get_localized(input));
+
+    Ok(composition(localized, VALUE_MD))
+}
+
 #[hdk_extern]
 fn get_record(hash: AnyDhtHash) -> ExternResult<Option<Record>> {
     get(hash, GetOptions::default())
@@ -127,43 +305,706 @@ fn deprecate_app(input: app::DeprecateInput) -> ExternResult<EntityResponse<AppE
     Ok(composition(entity, ENTITY_MD))
 }
 
+/// Replaces or reorders the screenshots of an app
 #[hdk_extern]
-fn get_apps_for_agent(input: GetForAgentInput) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
-    let (_, pathhash) = hc_utils::path(
-        ANCHOR_AGENTS,
-        vec![input.for_agent.to_string(), ANCHOR_APPS.to_string()],
-    );
-    let collection =
-        catch!(hc_crud::get_entities(&pathhash, LinkTypes::App, None).map_err(|e| e.into()));
+fn set_app_screenshots(input: app::SetScreenshotsInput) -> ExternResult<EntityResponse<AppEntry>> {
+    let entity = catch!(app::set_screenshots(input));
 
-    Ok(composition(collection, ENTITY_COLLECTION_MD))
+    Ok(composition(entity, ENTITY_MD))
 }
 
+/// App listings resolve their text for `locales` when it is given, like `get_localized_app`
 #[hdk_extern]
-fn get_my_apps(_: ()) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
-    get_apps_for_agent(GetForAgentInput {
-        for_agent: hc_utils::agentpubkey()?,
-    })
+fn get_apps_for_agent(
+    input: GetAppsForAgentInput,
+) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let collection = catch!(app::get_for_agent(&input.for_agent));
+    let delisted = catch!(delisting::get_delisted_ids());
+    let collection = collection
+        .into_iter()
+        .filter(|entity: &Entity<AppEntry>| !delisted.apps.contains(&entity.id))
+        .collect();
+
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
+}
+
+/// Gets the agent's own apps, including any that have been delisted
+#[hdk_extern]
+fn get_my_apps(input: Option<GetAppsInput>) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let input = input.unwrap_or_default();
+    let collection = catch!(app::get_for_agent(&hc_utils::agentpubkey()?));
+
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
 }
 
 #[hdk_extern]
-fn get_non_deprecated_apps(_: ()) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+fn get_non_deprecated_apps(
+    input: Option<GetAppsInput>,
+) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let input = input.unwrap_or_default();
     let (_, pathhash) = hc_utils::path_base(ANCHOR_APPS);
     let collection =
         catch!(hc_crud::get_entities(&pathhash, LinkTypes::App, None).map_err(|e| e.into()));
+    let delisted = catch!(delisting::get_delisted_ids());
     let collection = collection
         .into_iter()
-        .filter(|entity: &Entity<AppEntry>| entity.content.deprecation.is_none())
+        .filter(|entity: &Entity<AppEntry>| {
+            entity.content.deprecation.is_none() && !delisted.apps.contains(&entity.id)
+        })
         .collect();
 
-    Ok(composition(collection, ENTITY_COLLECTION_MD))
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
 }
 
 #[hdk_extern]
-fn get_all_apps(_: ()) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+fn get_all_apps(input: Option<GetAppsInput>) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let input = input.unwrap_or_default();
     let (_, pathhash) = hc_utils::path_base(ANCHOR_APPS);
     let collection =
         catch!(hc_crud::get_entities(&pathhash, LinkTypes::App, None).map_err(|e| e.into()));
+    let delisted = catch!(delisting::get_delisted_ids());
+    let collection = collection
+        .into_iter()
+        .filter(|entity: &Entity<AppEntry>| !delisted.apps.contains(&entity.id))
+        .collect();
+
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
+}
+
+// Favorite
+/// Bookmarks an app privately on this agent's source chain
+#[hdk_extern]
+fn add_favorite(input: GetEntityInput) -> ExternResult<Response<FavoriteEntry>> {
+    let favorite = catch!(favorite::add(input.id));
+
+    Ok(composition(favorite, VALUE_MD))
+}
+
+#[hdk_extern]
+fn remove_favorite(input: GetEntityInput) -> ExternResult<Response<Vec<ActionHash>>> {
+    let delete_hashes = catch!(favorite::remove(input.id));
+
+    Ok(composition(delete_hashes, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_my_favorites(_: ()) -> ExternResult<Response<Vec<FavoriteEntry>>> {
+    let collection = catch!(favorite::get_mine());
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+// Channel Preference
+#[derive(Debug, Deserialize)]
+pub struct SetAppChannelInput {
+    pub for_app: EntityId,
+    pub channel: ReleaseChannel,
+}
+
+/// Follows a release channel for an app privately; latest release lookups and update checks use it
+/// unless a channel is given
+#[hdk_extern]
+fn set_app_channel(input: SetAppChannelInput) -> ExternResult<Response<ChannelPreferenceEntry>> {
+    let preference = catch!(channel::set(input.for_app, input.channel));
+
+    Ok(composition(preference, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_app_channel(input: GetForAppInput) -> ExternResult<Response<ReleaseChannel>> {
+    let channel = catch!(channel::get(&input.for_app));
+
+    Ok(composition(channel, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_my_app_channels(_: ()) -> ExternResult<Response<Vec<ChannelPreferenceEntry>>> {
+    let collection = catch!(channel::get_mine());
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+// Update Check
+#[derive(Debug, Deserialize)]
+pub struct CheckForUpdatesInput {
+    pub installed: Vec<hash_index::InstalledHashes>,
+}
+
+/// Gets the current release and listing status for each installed app, identified by its hashes
+#[hdk_extern]
+fn check_for_updates(
+    input: CheckForUpdatesInput,
+) -> ExternResult<Response<Vec<hash_index::UpdateCheck>>> {
+    let checks = catch!(hash_index::check_for_updates(input.installed));
+
+    Ok(composition(checks, VALUE_MD))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAppsUsingDnaInput {
+    pub dna_hash: DnaHash,
+
+    // optional
+    pub locales: Option<Vec<String>>, // in order of preference
+}
+
+/// Gets the listed apps whose app or release hashes declare the given DNA
+#[hdk_extern]
+fn get_apps_using_dna(
+    input: GetAppsUsingDnaInput,
+) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let collection = catch!(hash_index::get_apps_using_dna(&input.dna_hash));
+
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
+}
+
+// Install
+#[derive(Debug, Deserialize)]
+pub struct GetPopularAppsInput {
+    pub limit: usize,
+
+    // optional
+    pub locales: Option<Vec<String>>, // in order of preference
+}
+
+/// Opt-in record that this agent installed an app; returns the app's install count
+#[hdk_extern]
+fn record_install(input: install::RecordInput) -> ExternResult<Response<u64>> {
+    let count = catch!(install::record(input));
+
+    Ok(composition(count, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_install_count(input: GetEntityInput) -> ExternResult<Response<u64>> {
+    let count = catch!(install::get_count(&input.id));
+
+    Ok(composition(count, VALUE_MD))
+}
+
+/// Gets the most installed apps, counting each installing agent once
+#[hdk_extern]
+fn get_popular_apps(
+    input: GetPopularAppsInput,
+) -> ExternResult<Response<Vec<install::PopularApp>>> {
+    let mut collection = catch!(install::get_popular(input.limit));
+    if let Some(ref locales) = input.locales {
+        for popular in collection.iter_mut() {
+            popular.app.content = popular.app.content.clone().localize(locales);
+        }
+    }
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+// App Draft
+/// Creates a private draft of an app listing that is not visible on the DHT
+#[hdk_extern]
+fn create_draft(input: draft::CreateInput) -> ExternResult<Response<draft::Draft>> {
+    let draft = catch!(draft::create(input));
+
+    Ok(composition(draft, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_draft(input: GetDraftInput) -> ExternResult<Response<draft::Draft>> {
+    let draft = catch!(draft::get(input.id));
+
+    Ok(composition(draft, VALUE_MD))
+}
+
+#[hdk_extern]
+fn update_draft(input: draft::UpdateInput) -> ExternResult<Response<draft::Draft>> {
+    let draft = catch!(draft::update(input));
+
+    Ok(composition(draft, VALUE_MD))
+}
+
+#[hdk_extern]
+fn delete_draft(input: GetDraftInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(draft::delete(input.id));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_my_drafts(_: ()) -> ExternResult<Response<Vec<draft::Draft>>> {
+    let collection = catch!(draft::get_mine());
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+/// Publishes a draft as a public AppEntry and removes the draft
+#[hdk_extern]
+fn publish_draft(input: GetDraftInput) -> ExternResult<EntityResponse<AppEntry>> {
+    let entity = catch!(draft::publish(input.id));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+// Scheduled Publication
+/// Schedules a draft or release to be published once the given time has passed
+#[hdk_extern]
+fn schedule_publish(input: schedule::CreateInput) -> ExternResult<Response<schedule::Scheduled>> {
+    let scheduled = catch!(schedule::create(input));
+
+    Ok(composition(scheduled, VALUE_MD))
+}
+
+/// Gets this agent's pending publications, soonest first
+///
+/// Publications that could not be published are included with their attempts and last error;
+/// once `failed` is set they are no longer retried and can only be canceled.
+#[hdk_extern]
+fn get_scheduled(_: ()) -> ExternResult<Response<Vec<schedule::Scheduled>>> {
+    let collection = catch!(schedule::get_pending());
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+#[hdk_extern]
+fn cancel_scheduled(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(schedule::cancel(input.id));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+/// Called by the conductor's scheduler; keeps running every minute while publications are pending
+#[hdk_extern(infallible)]
+fn publish_scheduled(_: Option<Schedule>) -> Option<Schedule> {
+    match schedule::publish_due() {
+        Ok(false) => None,
+        Ok(true) => Some(Schedule::Persisted(
+            schedule::PUBLISH_SCHEDULED_CRON.to_string(),
+        )),
+        Err(error) => {
+            debug!("Failed to publish scheduled targets: {:?}", error);
+            Some(Schedule::Persisted(
+                schedule::PUBLISH_SCHEDULED_CRON.to_string(),
+            ))
+        }
+    }
+}
+
+// Release
+#[hdk_extern]
+fn create_release(input: release::CreateInput) -> ExternResult<EntityResponse<ReleaseEntry>> {
+    let entity = catch!(release::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+/// Gets a release by id, even when it has been delisted
+#[hdk_extern]
+fn get_release(input: GetEntityInput) -> ExternResult<EntityResponse<ReleaseEntry>> {
+    let entity = catch!(release::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_release(input: release::UpdateInput) -> ExternResult<EntityResponse<ReleaseEntry>> {
+    let entity = catch!(release::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+/// Marks a release as yanked so latest and installable release lookups skip it
+#[hdk_extern]
+fn yank_release(input: release::YankInput) -> ExternResult<EntityResponse<ReleaseEntry>> {
+    let entity = catch!(release::yank(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn unyank_release(input: GetEntityInput) -> ExternResult<EntityResponse<ReleaseEntry>> {
+    let entity = catch!(release::unyank(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_releases_for_app(
+    input: GetForAppInput,
+) -> ExternResult<Response<Vec<Entity<ReleaseEntry>>>> {
+    let collection = catch!(release::get_for_app(&input.for_app));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+#[hdk_extern]
+fn get_latest_release_for_app(
+    input: GetLatestReleaseInput,
+) -> ExternResult<Response<Option<Entity<ReleaseEntry>>>> {
+    let channel = catch!(channel::resolve(&input.for_app, &input.channel));
+    let entity = catch!(release::get_latest(&input.for_app, &channel));
+
+    Ok(composition(entity, VALUE_MD))
+}
+
+/// Gets the newest release whose declared Holochain version range matches the given version
+#[hdk_extern]
+fn get_installable_release(
+    input: GetInstallableReleaseInput,
+) -> ExternResult<Response<Option<Entity<ReleaseEntry>>>> {
+    let channel = catch!(channel::resolve(&input.for_app, &input.channel));
+    let entity = catch!(release::get_installable(
+        &input.for_app,
+        &input.holochain_version,
+        &channel
+    ));
+
+    Ok(composition(entity, VALUE_MD))
+}
+
+// Delisting
+#[hdk_extern]
+fn create_delisting(input: delisting::CreateInput) -> ExternResult<EntityResponse<DelistingEntry>> {
+    let entity = catch!(delisting::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_delisting(input: GetEntityInput) -> ExternResult<EntityResponse<DelistingEntry>> {
+    let entity = catch!(delisting::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_delisting(input: delisting::UpdateInput) -> ExternResult<EntityResponse<DelistingEntry>> {
+    let entity = catch!(delisting::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn revoke_delisting(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(delisting::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_delistings(_: ()) -> ExternResult<Response<Vec<Entity<DelistingEntry>>>> {
+    let collection = catch!(delisting::get_all());
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+/// Gets the delistings of an app and its releases so launchers can warn users who installed it
+#[hdk_extern]
+fn get_delistings_for_app(
+    input: GetForAppInput,
+) -> ExternResult<Response<Vec<Entity<DelistingEntry>>>> {
+    let collection = catch!(delisting::get_for_app(&input.for_app));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+// Review
+#[hdk_extern]
+fn create_review(input: review::CreateInput) -> ExternResult<EntityResponse<ReviewEntry>> {
+    let entity = catch!(review::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_review(input: GetEntityInput) -> ExternResult<EntityResponse<ReviewEntry>> {
+    let entity = catch!(review::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_review(input: review::UpdateInput) -> ExternResult<EntityResponse<ReviewEntry>> {
+    let entity = catch!(review::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_review(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(review::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+/// Gets the reviews of an app, each with the publisher's response when there is one
+#[hdk_extern]
+fn get_reviews_for_app(
+    input: GetForAppInput,
+) -> ExternResult<Response<Vec<review::ReviewWithResponse>>> {
+    let collection = catch!(review::get_for_app(&input.for_app));
+
+    Ok(composition(collection, VALUE_MD))
+}
+
+/// Gets the review count, average and star distribution of an app
+#[hdk_extern]
+fn get_app_rating(input: GetForAppInput) -> ExternResult<Response<review::RatingSummary>> {
+    let summary = catch!(review::get_rating(&input.for_app));
+
+    Ok(composition(summary, VALUE_MD))
+}
+
+// Review Response
+#[hdk_extern]
+fn create_review_response(
+    input: review_response::CreateInput,
+) -> ExternResult<EntityResponse<ReviewResponseEntry>> {
+    let entity = catch!(review_response::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_review_response(input: GetEntityInput) -> ExternResult<EntityResponse<ReviewResponseEntry>> {
+    let entity = catch!(review_response::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_review_response(
+    input: review_response::UpdateInput,
+) -> ExternResult<EntityResponse<ReviewResponseEntry>> {
+    let entity = catch!(review_response::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_review_response(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(review_response::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+// Advisory
+#[derive(Debug, Deserialize)]
+pub struct CheckAdvisoriesInput {
+    pub installed_hashes: Vec<String>,
+}
+
+/// Creates a security advisory; only the app's editors or a steward may do so
+#[hdk_extern]
+fn create_advisory(input: advisory::CreateInput) -> ExternResult<EntityResponse<AdvisoryEntry>> {
+    let entity = catch!(advisory::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_advisory(input: GetEntityInput) -> ExternResult<EntityResponse<AdvisoryEntry>> {
+    let entity = catch!(advisory::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_advisory(input: advisory::UpdateInput) -> ExternResult<EntityResponse<AdvisoryEntry>> {
+    let entity = catch!(advisory::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_advisory(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(advisory::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_advisories_for_app(
+    input: GetForAppInput,
+) -> ExternResult<Response<Vec<Entity<AdvisoryEntry>>>> {
+    let collection = catch!(advisory::get_for_app(&input.for_app));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+/// Gets the advisories affecting the releases that match any of the installed hashes
+#[hdk_extern]
+fn check_advisories(
+    input: CheckAdvisoriesInput,
+) -> ExternResult<Response<Vec<advisory::AdvisoryMatch>>> {
+    let matches = catch!(advisory::check(input.installed_hashes));
+
+    Ok(composition(matches, VALUE_MD))
+}
+
+// Comment
+#[hdk_extern]
+fn create_comment(input: comment::CreateInput) -> ExternResult<EntityResponse<CommentEntry>> {
+    let entity = catch!(comment::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_comment(input: GetEntityInput) -> ExternResult<EntityResponse<CommentEntry>> {
+    let entity = catch!(comment::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_comment(input: comment::UpdateInput) -> ExternResult<EntityResponse<CommentEntry>> {
+    let entity = catch!(comment::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_comment(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(comment::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+/// Gets every version of a comment, oldest first
+#[hdk_extern]
+fn get_comment_history(
+    input: GetEntityInput,
+) -> ExternResult<Response<Vec<comment::CommentVersion>>> {
+    let history = catch!(comment::get_history(&input.id));
+
+    Ok(composition(history, VALUE_MD))
+}
+
+/// Gets a page of an app's top-level comments, or of the replies to a comment
+#[hdk_extern]
+fn get_comment_thread(
+    input: comment::GetThreadInput,
+) -> ExternResult<Response<comment::ThreadPage>> {
+    let page = catch!(comment::get_thread(input));
+
+    Ok(composition(page, VALUE_MD))
+}
+
+// Report
+#[hdk_extern]
+fn create_report(input: report::CreateInput) -> ExternResult<EntityResponse<ReportEntry>> {
+    let entity = catch!(report::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_report(input: GetEntityInput) -> ExternResult<EntityResponse<ReportEntry>> {
+    let entity = catch!(report::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_report(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(report::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+/// Gets the moderation queue, optionally only the reports for one app or publisher
+#[hdk_extern]
+fn get_reports(input: report::GetReportsInput) -> ExternResult<Response<Vec<Entity<ReportEntry>>>> {
+    let collection = catch!(report::get_reports(input));
+
+    Ok(composition(collection, ENTITY_COLLECTION_MD))
+}
+
+// Collection
+#[hdk_extern]
+fn create_collection(
+    input: collection::CreateInput,
+) -> ExternResult<EntityResponse<CollectionEntry>> {
+    let entity = catch!(collection::create(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn get_collection(input: GetEntityInput) -> ExternResult<EntityResponse<CollectionEntry>> {
+    let entity = catch!(collection::get(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn update_collection(
+    input: collection::UpdateInput,
+) -> ExternResult<EntityResponse<CollectionEntry>> {
+    let entity = catch!(collection::update(input));
+
+    Ok(composition(entity, ENTITY_MD))
+}
+
+#[hdk_extern]
+fn delete_collection(input: GetEntityInput) -> ExternResult<Response<ActionHash>> {
+    let delete_hash = catch!(collection::delete(input));
+
+    Ok(composition(delete_hash, VALUE_MD))
+}
+
+/// Gets all collections, official (steward-owned) collections first
+#[hdk_extern]
+fn get_collections(_: ()) -> ExternResult<Response<Vec<Entity<CollectionEntry>>>> {
+    let collection = catch!(collection::get_all());
 
     Ok(composition(collection, ENTITY_COLLECTION_MD))
 }
+
+/// Gets the listed apps of a collection in order
+#[hdk_extern]
+fn get_collection_apps(
+    input: collection::GetAppsInput,
+) -> ExternResult<Response<Vec<Entity<AppEntry>>>> {
+    let collection = catch!(collection::get_apps(&input.id));
+
+    Ok(composition(
+        app::localize_all(collection, &input.locales),
+        ENTITY_COLLECTION_MD,
+    ))
+}
+
+// Memory
+#[hdk_extern]
+fn create_memory(input: memory::CreateInput) -> ExternResult<Response<EntryHash>> {
+    let address = catch!(memory::create(input));
+
+    Ok(composition(address, VALUE_MD))
+}
+
+#[hdk_extern]
+fn get_memory(input: EntryHash) -> ExternResult<Response<MemoryEntry>> {
+    let memory = catch!(memory::get_memory(input));
+
+    Ok(composition(memory, VALUE_MD))
+}
+
+/// Gets the uncompressed bytes of the MemoryEntry at the given address
+#[hdk_extern]
+fn get_memory_bytes(input: EntryHash) -> ExternResult<Response<serde_bytes::ByteBuf>> {
+    let bytes = catch!(memory::get_bytes(input));
+
+    Ok(composition(serde_bytes::ByteBuf::from(bytes), VALUE_MD))
+}
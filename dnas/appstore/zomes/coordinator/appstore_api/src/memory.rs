@@ -0,0 +1,101 @@
+use crate::{AppError, AppResult, EntryTypes, UserError};
+use appstore::{CompressionCodec, MemoryBlockEntry, MemoryEntry, SequencePosition};
+use hc_crud::now;
+use hdk::prelude::*;
+
+// Entries are limited to 4MB so the stored bytes are split into blocks well below that
+const MEMORY_BLOCK_SIZE: usize = 2 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+
+    // optional
+    pub compression: Option<CompressionCodec>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<EntryHash> {
+    debug!("Creating Memory: {} bytes", input.bytes.len());
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let hash = hex::encode(mere_memory_types::calculate_hash(&input.bytes));
+    let memory_size = input.bytes.len() as u64;
+
+    let stored_bytes = match input.compression {
+        Some(ref codec) => codec.compress(&input.bytes)?,
+        None => input.bytes,
+    };
+    debug!(
+        "Storing {} bytes as {} bytes using compression: {:?}",
+        memory_size,
+        stored_bytes.len(),
+        input.compression
+    );
+
+    let chunks = stored_bytes.chunks(MEMORY_BLOCK_SIZE);
+    let length = chunks.len() as u64;
+    let mut block_addresses = vec![];
+
+    for (index, chunk) in chunks.enumerate() {
+        let block = MemoryBlockEntry {
+            sequence: SequencePosition {
+                position: index as u64 + 1,
+                length,
+            },
+            bytes: chunk.to_vec(),
+        };
+
+        block_addresses.push(hash_entry(&block)?);
+        create_entry(EntryTypes::MemoryBlock(block))?;
+    }
+
+    let memory = MemoryEntry {
+        author: pubkey,
+        published_at: now()?,
+        hash,
+        memory_size,
+        block_addresses,
+        compression: input.compression,
+    };
+    let address = hash_entry(&memory)?;
+    create_entry(EntryTypes::Memory(memory))?;
+
+    Ok(address)
+}
+
+fn get_app_entry<T>(address: &EntryHash) -> AppResult<T>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let record = get(address.to_owned(), GetOptions::default())?.ok_or(UserError::CustomError(
+        format!("Memory entry not found for address: {}", address),
+    ))?;
+
+    Ok(record
+        .entry()
+        .to_app_option::<T>()?
+        .ok_or(AppError::DeserializeError(format!(
+            "Address {} is not an app entry",
+            address
+        )))?)
+}
+
+pub fn get_memory(address: EntryHash) -> AppResult<MemoryEntry> {
+    debug!("Get memory: {}", address);
+    get_app_entry(&address)
+}
+
+/// Reassemble the blocks of a MemoryEntry and return the uncompressed bytes
+pub fn get_bytes(address: EntryHash) -> AppResult<Vec<u8>> {
+    let memory = get_memory(address)?;
+    let mut bytes = Vec::with_capacity(memory.memory_size as usize);
+
+    for block_address in memory.block_addresses.iter() {
+        let block: MemoryBlockEntry = get_app_entry(block_address)?;
+        bytes.extend(block.bytes);
+    }
+
+    Ok(memory
+        .decode_bytes(bytes)
+        .map_err(AppError::UnexpectedStateError)?)
+}
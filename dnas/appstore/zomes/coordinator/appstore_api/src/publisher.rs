@@ -1,4 +1,4 @@
-use crate::{AppResult, ANCHOR_AGENTS, ANCHOR_PUBLISHERS};
+use crate::{verification, AppResult, ANCHOR_AGENTS, ANCHOR_PUBLISHERS};
 use appstore::{
     DeprecationNotice, GetEntityInput, LinkTypes, LocationTriplet, PublisherEntry,
     UpdateEntityInput, WebAddress,
@@ -11,9 +11,10 @@ pub struct CreateInput {
     pub name: String,
     pub location: LocationTriplet,
     pub website: WebAddress,
-    pub icon_src: String,
 
     // optional
+    pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
     pub description: Option<String>,
     pub email: Option<String>,
     pub editors: Option<Vec<AgentPubKey>>,
@@ -40,7 +41,8 @@ pub fn create(mut input: CreateInput) -> AppResult<Entity<PublisherEntry>> {
         description: input.description,
         location: input.location,
         website: input.website,
-        icon_src: input.icon_src,
+        icon_src: input.icon_src.unwrap_or_default(),
+        icon: input.icon,
 
         editors: input.editors.unwrap_or(default_editors),
 
@@ -85,6 +87,23 @@ pub fn get(input: GetEntityInput) -> AppResult<Entity<PublisherEntry>> {
     Ok(entity)
 }
 
+#[derive(Debug, Serialize)]
+pub struct VerifiedPublisher {
+    pub publisher: Entity<PublisherEntry>,
+    pub verification: verification::VerificationStatus,
+}
+
+/// Get a publisher along with whether its current website domain is verified
+pub fn get_with_verification(input: GetEntityInput) -> AppResult<VerifiedPublisher> {
+    let publisher = get(input)?;
+    let verification = verification::get_status(&publisher)?;
+
+    Ok(VerifiedPublisher {
+        publisher,
+        verification,
+    })
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct UpdateProperties {
     pub name: Option<String>,
@@ -92,6 +111,7 @@ pub struct UpdateProperties {
     pub location: Option<LocationTriplet>,
     pub website: Option<WebAddress>,
     pub icon_src: Option<String>,
+    pub icon: Option<EntryHash>,
     pub email: Option<String>,
     pub editors: Option<Vec<AgentPubKey>>,
     pub published_at: Option<u64>,
@@ -112,7 +132,13 @@ pub fn update(input: UpdateInput) -> AppResult<Entity<PublisherEntry>> {
         current.description = props.description.or(current.description);
         current.location = props.location.unwrap_or(current.location);
         current.website = props.website.unwrap_or(current.website);
-        current.icon_src = props.icon_src.unwrap_or(current.icon_src);
+        if let Some(icon) = props.icon {
+            current.icon = Some(icon);
+            current.icon_src = String::new();
+        } else if let Some(icon_src) = props.icon_src {
+            current.icon = None;
+            current.icon_src = icon_src;
+        }
         current.email = props.email.or(current.email);
         current.published_at = props.published_at.unwrap_or(current.published_at);
         current.last_updated = props.last_updated.unwrap_or(current.last_updated);
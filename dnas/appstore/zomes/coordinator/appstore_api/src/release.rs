@@ -0,0 +1,273 @@
+use crate::{delisting, hash_index, AppResult, UserError, ANCHOR_APPS, ANCHOR_RELEASES};
+use appstore::{
+    AppEntry, EntityId, GetEntityInput, LinkTypes, ReleaseChannel, ReleaseEntry, RoleManifest,
+    UpdateEntityInput, YankNotice,
+};
+use hc_crud::{create_entity, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub app: EntityId,
+    pub version: String,
+    pub hashes: String,
+
+    // optional
+    pub changelog: Option<String>,
+    pub metadata: Option<String>,
+    pub roles: Option<Vec<RoleManifest>>,
+    pub holochain_versions: Option<String>,
+    pub channel: Option<ReleaseChannel>,
+
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+}
+
+/// Releases can only be published to the channels that the app currently offers
+fn check_channel_offered(app: &EntityId, channel: &ReleaseChannel) -> AppResult<()> {
+    let app_entity: Entity<AppEntry> = get_entity(app)?;
+
+    if !app_entity.content.offered_channels().contains(channel) {
+        Err(UserError::CustomError(format!(
+            "App {} does not offer the {:?} channel",
+            app, channel
+        )))?;
+    }
+
+    Ok(())
+}
+
+/// Check what release validation requires before anything is written
+///
+/// Scheduled publications run unattended, so a release that would be rejected must fail here
+/// instead of leaving some of its writes behind.
+fn check_publishable(release: &ReleaseEntry) -> AppResult<()> {
+    let app_entity: Entity<AppEntry> = get_entity(&release.app)?;
+
+    if !app_entity.content.editors.contains(&release.author) {
+        Err(UserError::CustomError(format!(
+            "Agent {} is not an editor of App {}",
+            release.author, release.app
+        )))?;
+    }
+
+    check_channel_offered(&release.app, &release.channel)?;
+
+    if release.version.trim().is_empty() {
+        Err(UserError::CustomError(
+            "Release version cannot be empty".to_string(),
+        ))?;
+    }
+
+    release
+        .holochain_requirement()
+        .map_err(UserError::CustomError)?;
+    appstore::validate_role_manifests(&release.roles).map_err(UserError::CustomError)?;
+
+    Ok(())
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<ReleaseEntry>> {
+    debug!("Creating Release {} for App: {}", input.version, input.app);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let release = ReleaseEntry {
+        app: input.app.clone(),
+        version: input.version,
+        hashes: input.hashes,
+        changelog: input.changelog,
+
+        author: pubkey,
+        published_at: input.published_at.unwrap_or(default_now),
+        last_updated: input.last_updated.unwrap_or(default_now),
+        metadata: input.metadata,
+        roles: input.roles.unwrap_or_default(),
+        holochain_versions: input.holochain_versions,
+        channel: input.channel.unwrap_or_default(),
+        yanked: None,
+        yank_app_action: None,
+    };
+    check_publishable(&release)?;
+
+    let entity = create_entity(&release)?;
+
+    {
+        // Path via App's Releases
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_APPS,
+            vec![input.app.to_string(), ANCHOR_RELEASES.to_string()],
+        );
+        entity.link_from(&pathhash, LinkTypes::Release, None)?;
+    }
+    hash_index::index_release(&entity, None)?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<ReleaseEntry>> {
+    debug!("Get release: {}", input.id);
+    let entity: Entity<ReleaseEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub version: Option<String>,
+    pub hashes: Option<String>,
+    pub changelog: Option<String>,
+    pub metadata: Option<String>,
+    pub roles: Option<Vec<RoleManifest>>,
+    pub holochain_versions: Option<String>,
+    pub channel: Option<ReleaseChannel>,
+    pub published_at: Option<u64>,
+    pub last_updated: Option<u64>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<ReleaseEntry>> {
+    debug!("Updating Release: {}", input.base);
+    let props = input.properties.clone();
+    let previous: Entity<ReleaseEntry> = get_entity(&input.base)?;
+    if let Some(channel) = &props.channel {
+        check_channel_offered(&previous.content.app, channel)?;
+    }
+
+    let entity = update_entity(&input.base, |mut current: ReleaseEntry, _| {
+        current.version = props.version.unwrap_or(current.version);
+        current.hashes = props.hashes.unwrap_or(current.hashes);
+        current.changelog = props.changelog.or(current.changelog);
+        current.metadata = props.metadata;
+        current.roles = props.roles.unwrap_or(current.roles);
+        current.holochain_versions = props.holochain_versions.or(current.holochain_versions);
+        current.channel = props.channel.unwrap_or(current.channel);
+        current.published_at = props.published_at.unwrap_or(current.published_at);
+        current.last_updated = props.last_updated.unwrap_or(current.last_updated);
+
+        Ok(current)
+    })?;
+
+    hash_index::index_release(&entity, Some(&previous.content))?;
+
+    Ok(entity)
+}
+
+/// The latest revision of the release's app, whose editors are allowed to (un)yank it
+fn get_app_action(release: &ActionHash) -> AppResult<ActionHash> {
+    let release: Entity<ReleaseEntry> = get_entity(release)?;
+    let app: Entity<AppEntry> = get_entity(&release.content.app)?;
+
+    Ok(app.action)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YankInput {
+    pub base: ActionHash,
+    pub reason: String,
+}
+
+/// Withdraws a release without deleting it so it stays fetchable by id
+pub fn yank(input: YankInput) -> AppResult<Entity<ReleaseEntry>> {
+    debug!("Yanking Release: {}", input.base);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let app_action = get_app_action(&input.base)?;
+
+    let entity = update_entity(&input.base, |mut current: ReleaseEntry, _| {
+        current.yanked = Some(YankNotice {
+            reason: input.reason.to_owned(),
+            yanked_by: pubkey.to_owned(),
+            yanked_at: default_now,
+        });
+        current.yank_app_action = Some(app_action.to_owned());
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+pub fn unyank(input: GetEntityInput) -> AppResult<Entity<ReleaseEntry>> {
+    debug!("Unyanking Release: {}", input.id);
+    let default_now = now()?;
+    let app_action = get_app_action(&input.id)?;
+
+    let entity = update_entity(&input.id, |mut current: ReleaseEntry, _| {
+        current.yanked = None;
+        current.yank_app_action = Some(app_action.to_owned());
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+/// Gets every release of an app, including delisted ones
+///
+/// Releases that no longer resolve are skipped so one bad link cannot hide the others.
+pub fn get_all_for_app(app: &EntityId) -> AppResult<Vec<Entity<ReleaseEntry>>> {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_RELEASES.to_string()],
+    );
+
+    let mut releases = vec![];
+    for link in get_links(pathhash, LinkTypes::Release, None)? {
+        if let Some(id) = link.target.into_action_hash() {
+            match get_entity(&id) {
+                Ok(entity) => releases.push(entity),
+                Err(error) => debug!("Skipping unresolvable release {}: {:?}", id, error),
+            }
+        }
+    }
+
+    Ok(releases)
+}
+
+/// Gets the releases of an app that have not been delisted, newest first
+///
+/// Yanked releases are included (with their notice) but never resolved as latest or installable.
+pub fn get_for_app(app: &EntityId) -> AppResult<Vec<Entity<ReleaseEntry>>> {
+    let delisted = delisting::get_delisted_ids()?;
+    let mut releases: Vec<Entity<ReleaseEntry>> = get_all_for_app(app)?
+        .into_iter()
+        .filter(|entity| !delisted.releases.contains(&entity.id))
+        .collect();
+
+    releases.sort_by(|a, b| b.content.published_at.cmp(&a.content.published_at));
+
+    Ok(releases)
+}
+
+/// Gets the newest release offered to agents following `channel`
+pub fn get_latest(
+    app: &EntityId,
+    channel: &ReleaseChannel,
+) -> AppResult<Option<Entity<ReleaseEntry>>> {
+    debug!("Get latest {:?} release for App: {}", channel, app);
+
+    Ok(get_for_app(app)?.into_iter().find(|release| {
+        release.content.yanked.is_none() && channel.includes(&release.content.channel)
+    }))
+}
+
+/// Gets the newest release that declares support for the given Holochain version
+pub fn get_installable(
+    app: &EntityId,
+    holochain_version: &str,
+    channel: &ReleaseChannel,
+) -> AppResult<Option<Entity<ReleaseEntry>>> {
+    debug!(
+        "Get installable release for App: {} on Holochain {}",
+        app, holochain_version
+    );
+    let version = appstore::parse_version(holochain_version).map_err(UserError::CustomError)?;
+
+    Ok(get_for_app(app)?.into_iter().find(|release| {
+        release.content.yanked.is_none()
+            && channel.includes(&release.content.channel)
+            && release.content.supports_holochain(&version)
+    }))
+}
@@ -0,0 +1,80 @@
+use crate::{AppResult, ANCHOR_MODERATION, ANCHOR_REPORTS};
+use appstore::{GetEntityInput, LinkTypes, ReportEntry, ReportReason, ReportTarget};
+use hc_crud::{create_entity, get_entities, get_entity, now, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub target: ReportTarget,
+    pub reason: ReportReason,
+    pub details: String,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+fn reports_path() -> EntryHash {
+    let (_, pathhash) = hc_utils::path(ANCHOR_MODERATION, vec![ANCHOR_REPORTS]);
+
+    pathhash
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<ReportEntry>> {
+    debug!(
+        "Creating Report ({:?}) for: {:?}",
+        input.reason, input.target
+    );
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+
+    let report = ReportEntry {
+        target: input.target,
+        reason: input.reason,
+        details: input.details,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&report)?;
+
+    // Path via Moderation Queue
+    entity.link_from(&reports_path(), LinkTypes::Report, None)?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<ReportEntry>> {
+    debug!("Get report: {}", input.id);
+    let entity: Entity<ReportEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Report: {}", input.id);
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    hc_utils::delete_links_to(&reports_path(), LinkTypes::Report, &input.id)?;
+
+    Ok(delete_hash)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetReportsInput {
+    // optional
+    pub target: Option<ReportTarget>,
+}
+
+pub fn get_reports(input: GetReportsInput) -> AppResult<Vec<Entity<ReportEntry>>> {
+    let reports: Vec<Entity<ReportEntry>> = get_entities(&reports_path(), LinkTypes::Report, None)?;
+
+    Ok(match input.target {
+        Some(target) => reports
+            .into_iter()
+            .filter(|entity| entity.content.target == target)
+            .collect(),
+        None => reports,
+    })
+}
@@ -0,0 +1,217 @@
+use crate::{review_response, AppResult, ANCHOR_AGENTS, ANCHOR_APPS, ANCHOR_REVIEWS};
+use appstore::{
+    AppEntry, EntityId, GetEntityInput, LinkTypes, ReviewEntry, ReviewResponseEntry,
+    UpdateEntityInput,
+};
+use hc_crud::{create_entity, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub app: EntityId,
+    pub rating: u8,
+    pub text: String,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<ReviewEntry>> {
+    debug!("Creating Review for App: {}", input.app);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let app: Entity<AppEntry> = get_entity(&input.app)?;
+
+    let review = ReviewEntry {
+        app: input.app.clone(),
+        app_action: app.action,
+        rating: input.rating,
+        text: input.text,
+
+        author: pubkey.clone(),
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&review)?;
+
+    {
+        // Path via App's Reviews
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_APPS,
+            vec![input.app.to_string(), ANCHOR_REVIEWS.to_string()],
+        );
+        entity.link_from(&pathhash, LinkTypes::Review, None)?;
+    }
+    {
+        // Path via Agent's Reviews
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_AGENTS,
+            vec![pubkey.to_string(), ANCHOR_REVIEWS.to_string()],
+        );
+        entity.link_from(&pathhash, LinkTypes::Review, None)?;
+    }
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<ReviewEntry>> {
+    debug!("Get review: {}", input.id);
+    let entity: Entity<ReviewEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub rating: Option<u8>,
+    pub text: Option<String>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<ReviewEntry>> {
+    debug!("Updating Review: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+
+    let previous: Entity<ReviewEntry> = get_entity(&input.base)?;
+    let app: Entity<AppEntry> = get_entity(&previous.content.app)?;
+
+    let entity = update_entity(&input.base, |mut current: ReviewEntry, _| {
+        current.app_action = app.action.to_owned();
+        current.rating = props.rating.unwrap_or(current.rating);
+        current.text = props.text.unwrap_or(current.text);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Review: {}", input.id);
+    let entity: Entity<ReviewEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    {
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_APPS,
+            vec![entity.content.app.to_string(), ANCHOR_REVIEWS.to_string()],
+        );
+        hc_utils::delete_links_to(&pathhash, LinkTypes::Review, &input.id)?;
+    }
+    {
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_AGENTS,
+            vec![
+                entity.content.author.to_string(),
+                ANCHOR_REVIEWS.to_string(),
+            ],
+        );
+        hc_utils::delete_links_to(&pathhash, LinkTypes::Review, &input.id)?;
+    }
+
+    Ok(delete_hash)
+}
+
+/// Get the reviews linked from an app, at most one per author
+///
+/// Review links are not validated, so a review linked more than once or from another app's path is
+/// only counted where it belongs.  When an author somehow has several reviews, the newest is kept.
+fn get_entities_for_app(app: &EntityId) -> AppResult<Vec<Entity<ReviewEntry>>> {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_APPS,
+        vec![app.to_string(), ANCHOR_REVIEWS.to_string()],
+    );
+    let mut reviews: Vec<Entity<ReviewEntry>> = vec![];
+
+    for link in get_links(pathhash, LinkTypes::Review, None)? {
+        let id = match link.target.into_action_hash() {
+            Some(id) => id,
+            None => continue,
+        };
+        if reviews.iter().any(|review| review.id == id) {
+            continue;
+        }
+
+        let review: Entity<ReviewEntry> = match get_entity(&id) {
+            Ok(review) => review,
+            Err(error) => {
+                debug!("Skipping unresolvable review {}: {:?}", id, error);
+                continue;
+            }
+        };
+        if &review.content.app != app {
+            continue;
+        }
+
+        match reviews
+            .iter_mut()
+            .find(|other| other.content.author == review.content.author)
+        {
+            Some(other) => {
+                if review.content.published_at > other.content.published_at {
+                    *other = review;
+                }
+            }
+            None => reviews.push(review),
+        }
+    }
+
+    Ok(reviews)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReviewWithResponse {
+    pub review: Entity<ReviewEntry>,
+    pub response: Option<Entity<ReviewResponseEntry>>,
+}
+
+pub fn get_for_app(app: &EntityId) -> AppResult<Vec<ReviewWithResponse>> {
+    let mut reviews = vec![];
+
+    for review in get_entities_for_app(app)? {
+        let response = review_response::get_for_review(&review.id)?;
+        reviews.push(ReviewWithResponse { review, response });
+    }
+
+    Ok(reviews)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RatingSummary {
+    pub count: u64,
+    pub average: f64,
+    pub distribution: [u64; 5], // number of 1 to 5 star ratings
+}
+
+pub fn get_rating(app: &EntityId) -> AppResult<RatingSummary> {
+    debug!("Get rating for App: {}", app);
+    let mut distribution = [0; 5];
+    let mut total = 0;
+
+    for review in get_entities_for_app(app)?.iter() {
+        let rating = review.content.rating as usize;
+
+        if (1..=5).contains(&rating) {
+            distribution[rating - 1] += 1;
+            total += rating as u64;
+        }
+    }
+
+    let count: u64 = distribution.iter().sum();
+    let average = if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    };
+
+    Ok(RatingSummary {
+        count,
+        average,
+        distribution,
+    })
+}
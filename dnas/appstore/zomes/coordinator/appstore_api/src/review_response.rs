@@ -0,0 +1,117 @@
+use crate::{AppResult, ANCHOR_RESPONSES, ANCHOR_REVIEWS};
+use appstore::{
+    AppEntry, EntityId, GetEntityInput, LinkTypes, PublisherEntry, ReviewEntry,
+    ReviewResponseEntry, UpdateEntityInput,
+};
+use hc_crud::{create_entity, get_entities, get_entity, now, update_entity, Entity};
+use hdk::prelude::*;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub review: EntityId,
+    pub text: String,
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Entity<ReviewResponseEntry>> {
+    debug!("Creating Response for Review: {}", input.review);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let publisher = get_publisher_for_review(&input.review)?;
+
+    let response = ReviewResponseEntry {
+        review: input.review.clone(),
+        publisher_action: publisher.action,
+        text: input.text,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&response)?;
+
+    {
+        // Path via Review's Responses
+        let (_, pathhash) = hc_utils::path(
+            ANCHOR_REVIEWS,
+            vec![input.review.to_string(), ANCHOR_RESPONSES.to_string()],
+        );
+        entity.link_from(&pathhash, LinkTypes::ReviewResponse, None)?;
+    }
+
+    Ok(entity)
+}
+
+fn get_publisher_for_review(review: &EntityId) -> AppResult<Entity<PublisherEntry>> {
+    let review: Entity<ReviewEntry> = get_entity(review)?;
+    let app: Entity<AppEntry> = get_entity(&review.content.app)?;
+
+    Ok(get_entity(&app.content.publisher)?)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<ReviewResponseEntry>> {
+    debug!("Get review response: {}", input.id);
+    let entity: Entity<ReviewResponseEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProperties {
+    pub text: Option<String>,
+    pub metadata: Option<String>,
+}
+pub type UpdateInput = UpdateEntityInput<UpdateProperties>;
+
+pub fn update(input: UpdateInput) -> AppResult<Entity<ReviewResponseEntry>> {
+    debug!("Updating Review Response: {}", input.base);
+    let props = input.properties.clone();
+    let default_now = now()?;
+    let previous: Entity<ReviewResponseEntry> = get_entity(&input.base)?;
+    let publisher = get_publisher_for_review(&previous.content.review)?;
+
+    let entity = update_entity(&input.base, |mut current: ReviewResponseEntry, _| {
+        current.publisher_action = publisher.action.to_owned();
+        current.text = props.text.unwrap_or(current.text);
+        current.metadata = props.metadata;
+        current.last_updated = default_now;
+
+        Ok(current)
+    })?;
+
+    Ok(entity)
+}
+
+pub fn delete(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Deleting Review Response: {}", input.id);
+    let entity: Entity<ReviewResponseEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_REVIEWS,
+        vec![
+            entity.content.review.to_string(),
+            ANCHOR_RESPONSES.to_string(),
+        ],
+    );
+    hc_utils::delete_links_to(&pathhash, LinkTypes::ReviewResponse, &input.id)?;
+
+    Ok(delete_hash)
+}
+
+/// Gets the most recent response to the given review, if there is one
+pub fn get_for_review(review: &EntityId) -> AppResult<Option<Entity<ReviewResponseEntry>>> {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_REVIEWS,
+        vec![review.to_string(), ANCHOR_RESPONSES.to_string()],
+    );
+    let responses: Vec<Entity<ReviewResponseEntry>> =
+        get_entities(&pathhash, LinkTypes::ReviewResponse, None)?;
+
+    Ok(responses
+        .into_iter()
+        .max_by_key(|entity| entity.content.published_at))
+}
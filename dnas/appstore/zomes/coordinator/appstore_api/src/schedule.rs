@@ -0,0 +1,219 @@
+use crate::{draft, release, AppResult, EntryTypes, UserError};
+use appstore::{EntryTypesUnit, PendingRelease, ScheduledPublicationEntry, ScheduledTarget};
+use hc_crud::now;
+use hdk::prelude::*;
+use std::collections::BTreeMap;
+
+pub const PUBLISH_SCHEDULED_FN: &'static str = "publish_scheduled";
+pub const PUBLISH_SCHEDULED_CRON: &'static str = "0 * * * * *"; // Check for due publications every minute
+
+#[derive(Debug, Serialize)]
+pub struct Scheduled {
+    pub id: ActionHash,     // Action hash of the first version
+    pub action: ActionHash, // Action hash of the latest version; failed attempts update the entry
+    pub content: ScheduledPublicationEntry,
+    pub failed: bool, // No longer retried; see `content.last_error`
+}
+
+/// Get the pending publications on this agent's source chain
+///
+/// Scheduled publications are private entries so this only reads the local source chain.
+fn query_scheduled() -> AppResult<Vec<Scheduled>> {
+    let deleted: Vec<ActionHash> = query(ChainQueryFilter::new().action_type(ActionType::Delete))?
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    // Map each version's action hash to the scheduled id while walking the chain in order
+    let mut scheduled_ids: BTreeMap<ActionHash, ActionHash> = BTreeMap::new();
+    let mut scheduled: BTreeMap<ActionHash, Scheduled> = BTreeMap::new();
+
+    for record in query(
+        ChainQueryFilter::new()
+            .entry_type(EntryTypesUnit::ScheduledPublication.try_into()?)
+            .include_entries(true),
+    )? {
+        let action = record.action_address().to_owned();
+        let id = match record.action() {
+            Action::Create(_) => action.clone(),
+            Action::Update(update) => match scheduled_ids.get(&update.original_action_address) {
+                Some(id) => id.to_owned(),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        if let Some(content) = record
+            .entry()
+            .to_app_option::<ScheduledPublicationEntry>()?
+        {
+            scheduled_ids.insert(action.clone(), id.clone());
+            scheduled.insert(
+                id.clone(),
+                Scheduled {
+                    id,
+                    action,
+                    failed: content.has_failed(),
+                    content,
+                },
+            );
+        }
+    }
+
+    Ok(scheduled
+        .into_values()
+        .filter(|scheduled| !deleted.contains(&scheduled.action))
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub target: ScheduledTarget,
+    pub at: u64,
+}
+
+pub fn create(input: CreateInput) -> AppResult<Scheduled> {
+    debug!("Scheduling {:?} for: {}", input.target, input.at);
+    let default_now = now()?;
+
+    if input.at < default_now {
+        Err(UserError::CustomError(format!(
+            "Cannot schedule a publication in the past ({} < {})",
+            input.at, default_now
+        )))?;
+    }
+
+    if let ScheduledTarget::Draft(ref id) = input.target {
+        // Make sure the draft exists before scheduling it
+        draft::get(id.to_owned())?;
+    }
+
+    let scheduled = ScheduledPublicationEntry {
+        target: input.target,
+        publish_at: input.at,
+        created_at: default_now,
+        attempts: 0,
+        last_error: None,
+    };
+    let id = create_entry(EntryTypes::ScheduledPublication(scheduled.clone()))?;
+
+    schedule(PUBLISH_SCHEDULED_FN)?;
+
+    Ok(Scheduled {
+        id: id.clone(),
+        action: id,
+        content: scheduled,
+        failed: false,
+    })
+}
+
+pub fn get_pending() -> AppResult<Vec<Scheduled>> {
+    let mut scheduled = query_scheduled()?;
+    scheduled.sort_by(|a, b| a.content.publish_at.cmp(&b.content.publish_at));
+
+    Ok(scheduled)
+}
+
+pub fn cancel(id: ActionHash) -> AppResult<ActionHash> {
+    debug!("Canceling scheduled publication: {}", id);
+    let scheduled = query_scheduled()?
+        .into_iter()
+        .find(|scheduled| scheduled.id == id)
+        .ok_or(UserError::CustomError(format!(
+            "No pending publication for id: {}",
+            id
+        )))?;
+
+    Ok(delete_entry(scheduled.action)?)
+}
+
+/// Find a release that an earlier attempt already published for this pending release
+///
+/// A failed attempt can still leave its release behind, so retries must not publish it twice.
+fn find_published_release(pending: &PendingRelease) -> AppResult<Option<ActionHash>> {
+    let pubkey = agent_info()?.agent_initial_pubkey;
+
+    Ok(release::get_all_for_app(&pending.app)?
+        .into_iter()
+        .find(|entity| entity.content.author == pubkey && entity.content.version == pending.version)
+        .map(|entity| entity.id))
+}
+
+fn publish_target(target: ScheduledTarget) -> AppResult<ActionHash> {
+    Ok(match target {
+        ScheduledTarget::Draft(id) => draft::publish(id)?.id,
+        ScheduledTarget::Release(pending) => {
+            if let Some(id) = find_published_release(&pending)? {
+                return Ok(id);
+            }
+
+            release::create(release::CreateInput {
+                app: pending.app,
+                version: pending.version,
+                hashes: pending.hashes,
+                changelog: pending.changelog,
+                metadata: pending.metadata,
+                roles: Some(pending.roles),
+                holochain_versions: pending.holochain_versions,
+                channel: Some(pending.channel),
+
+                published_at: None,
+                last_updated: None,
+            })?
+            .id
+        }
+    })
+}
+
+/// Publish the earliest scheduled target whose time has passed; returns true when some are still
+/// pending
+///
+/// Everything written in a zome call is committed together, so only one target is published per
+/// call; a target that fails at commit cannot roll back the publication or the bookkeeping of
+/// another.  A publication that fails records the error on its entry and is retried on a later run,
+/// until it has failed `SCHEDULED_PUBLICATION_ATTEMPT_LIMIT` times.  Failed publications stay
+/// listed until they are canceled.
+pub fn publish_due() -> AppResult<bool> {
+    let default_now = now()?;
+    let mut remaining: Vec<Scheduled> = query_scheduled()?
+        .into_iter()
+        .filter(|scheduled| !scheduled.failed)
+        .collect();
+    remaining.sort_by(|a, b| a.content.publish_at.cmp(&b.content.publish_at));
+
+    if remaining.is_empty() {
+        return Ok(false);
+    }
+    let scheduled = remaining.remove(0);
+    if scheduled.content.publish_at > default_now {
+        return Ok(true);
+    }
+    let mut pending = !remaining.is_empty();
+
+    match publish_target(scheduled.content.target.clone()) {
+        Ok(id) => {
+            debug!("Published scheduled target {} as: {}", scheduled.id, id);
+            delete_entry(scheduled.action)?;
+        }
+        Err(error) => {
+            debug!(
+                "Failed to publish scheduled target {}: {:?}",
+                scheduled.id, error
+            );
+            let mut content = scheduled.content;
+            content.attempts += 1;
+            content.last_error = Some(error.to_string());
+
+            if !content.has_failed() {
+                pending = true;
+            }
+
+            update_entry(scheduled.action, EntryTypes::ScheduledPublication(content))?;
+        }
+    }
+
+    Ok(pending)
+}
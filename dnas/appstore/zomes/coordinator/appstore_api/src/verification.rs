@@ -0,0 +1,180 @@
+use crate::{AppResult, UserError, ANCHOR_PUBLISHERS, ANCHOR_VERIFICATIONS};
+use appstore::{
+    parse_verification_proof, verification_challenge, verification_proof, website_domain, EntityId,
+    GetEntityInput, LinkTypes, PublisherEntry, VerificationEntry, VERIFICATION_WELL_KNOWN_PATH,
+};
+use hc_crud::{create_entity, get_entities, get_entity, now, Entity};
+use hdk::prelude::*;
+
+fn publisher_verifications_path(publisher: &EntityId) -> EntryHash {
+    let (_, pathhash) = hc_utils::path(
+        ANCHOR_PUBLISHERS,
+        vec![publisher.to_string(), ANCHOR_VERIFICATIONS.to_string()],
+    );
+
+    pathhash
+}
+
+fn get_publisher_domain(publisher: &Entity<PublisherEntry>) -> AppResult<String> {
+    Ok(
+        website_domain(&publisher.content.website.url).ok_or(UserError::CustomError(format!(
+            "Publisher website '{}' does not have a domain",
+            publisher.content.website.url
+        )))?,
+    )
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerificationChallenge {
+    pub publisher: EntityId,
+    pub publisher_agent: AgentPubKey,
+    pub challenge: String,
+    pub signature: Signature,
+    pub signature_hex: String,
+    pub proof: String, // `<challenge>\n<signature_hex>`, to be hosted at `well_known_url`
+    pub well_known_url: String,
+}
+
+/// Sign the challenge that proves this agent controls the publisher's website
+pub fn create_challenge(input: GetEntityInput) -> AppResult<VerificationChallenge> {
+    debug!(
+        "Creating verification challenge for Publisher: {}",
+        input.id
+    );
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let publisher: Entity<PublisherEntry> = get_entity(&input.id)?;
+    let domain = get_publisher_domain(&publisher)?;
+
+    let challenge = verification_challenge(&pubkey, &publisher.id, &domain);
+    let signature = sign_raw(pubkey.clone(), challenge.clone().into_bytes())?;
+
+    Ok(VerificationChallenge {
+        publisher: publisher.id,
+        publisher_agent: pubkey,
+        proof: verification_proof(&challenge, &signature),
+        challenge,
+        signature_hex: hex::encode(signature.0),
+        signature,
+        well_known_url: format!("https://{}{}", domain, VERIFICATION_WELL_KNOWN_PATH),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInput {
+    pub publisher: EntityId,
+    pub publisher_agent: AgentPubKey,
+    pub proof: String, // As fetched from the publisher's well-known URL
+
+    // optional
+    pub metadata: Option<String>,
+}
+
+/// Record a steward's attestation after fetching the challenge from the publisher's domain
+///
+/// Fetching happens outside of the DNA (eg. a steward's local verification service) because zomes
+/// cannot make HTTP requests.
+pub fn create(input: CreateInput) -> AppResult<Entity<VerificationEntry>> {
+    debug!("Verifying Publisher: {}", input.publisher);
+    let pubkey = agent_info()?.agent_initial_pubkey;
+    let default_now = now()?;
+    let publisher: Entity<PublisherEntry> = get_entity(&input.publisher)?;
+    let domain = get_publisher_domain(&publisher)?;
+    let signature =
+        parse_verification_proof(&input.proof, &input.publisher_agent, &publisher.id, &domain)
+            .map_err(UserError::CustomError)?;
+
+    let verification = VerificationEntry {
+        publisher: input.publisher.clone(),
+        publisher_action: publisher.action.to_owned(),
+        domain,
+        publisher_agent: input.publisher_agent,
+        signature,
+
+        author: pubkey,
+        published_at: default_now,
+        last_updated: default_now,
+        metadata: input.metadata,
+    };
+    let entity = create_entity(&verification)?;
+
+    // Path via Publisher's Verifications
+    entity.link_from(
+        &publisher_verifications_path(&input.publisher),
+        LinkTypes::Verification,
+        None,
+    )?;
+
+    Ok(entity)
+}
+
+pub fn get(input: GetEntityInput) -> AppResult<Entity<VerificationEntry>> {
+    debug!("Get verification: {}", input.id);
+    let entity: Entity<VerificationEntry> = get_entity(&input.id)?;
+
+    Ok(entity)
+}
+
+pub fn revoke(input: GetEntityInput) -> AppResult<ActionHash> {
+    debug!("Revoking Verification: {}", input.id);
+    let entity: Entity<VerificationEntry> = get_entity(&input.id)?;
+    let delete_hash = delete_entry(input.id.clone())?;
+
+    hc_utils::delete_links_to(
+        &publisher_verifications_path(&entity.content.publisher),
+        LinkTypes::Verification,
+        &input.id,
+    )?;
+
+    Ok(delete_hash)
+}
+
+pub fn get_for_publisher(publisher: &EntityId) -> AppResult<Vec<Entity<VerificationEntry>>> {
+    Ok(get_entities(
+        &publisher_verifications_path(publisher),
+        LinkTypes::Verification,
+        None,
+    )?)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VerificationStatus {
+    Unverified,
+    Verified {
+        domain: String,
+        verification: EntityId,
+        verified_at: u64,
+    },
+    Stale {
+        domain: String, // The verified domain no longer matches the publisher's website
+        verification: EntityId,
+        verified_at: u64,
+    },
+}
+
+/// Compare the publisher's verifications with the domain of its latest website
+pub fn get_status(publisher: &Entity<PublisherEntry>) -> AppResult<VerificationStatus> {
+    let current_domain = website_domain(&publisher.content.website.url);
+    let mut verifications = get_for_publisher(&publisher.id)?;
+    verifications.sort_by_key(|entity| std::cmp::Reverse(entity.content.published_at));
+
+    if let Some(verification) = verifications
+        .iter()
+        .find(|entity| Some(&entity.content.domain) == current_domain.as_ref())
+    {
+        return Ok(VerificationStatus::Verified {
+            domain: verification.content.domain.to_owned(),
+            verification: verification.id.to_owned(),
+            verified_at: verification.content.published_at,
+        });
+    }
+
+    Ok(match verifications.into_iter().next() {
+        Some(verification) => VerificationStatus::Stale {
+            domain: verification.content.domain,
+            verification: verification.id,
+            verified_at: verification.content.published_at,
+        },
+        None => VerificationStatus::Unverified,
+    })
+}
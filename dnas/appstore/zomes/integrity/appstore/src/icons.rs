@@ -0,0 +1,536 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use imagesize::ImageType;
+
+const ICON_DIMENSION_LIMIT: usize = 300; // Maximum width or height in pixels
+
+const MIME_PNG: &'static str = "image/png";
+const MIME_JPEG: &'static str = "image/jpeg";
+const MIME_WEBP: &'static str = "image/webp";
+const MIME_SVG: &'static str = "image/svg+xml";
+
+#[derive(Debug, PartialEq)]
+pub enum IconFormat {
+    Png,
+    Jpeg,
+    Webp,
+    Svg,
+}
+
+impl IconFormat {
+    fn from_mime_type(mime_type: &str) -> Result<Self, String> {
+        match mime_type {
+            MIME_PNG => Ok(IconFormat::Png),
+            MIME_JPEG => Ok(IconFormat::Jpeg),
+            MIME_WEBP => Ok(IconFormat::Webp),
+            MIME_SVG => Ok(IconFormat::Svg),
+            other => Err(format!(
+                "Icon MIME type '{}' is not allowed; expected one of: {}, {}, {}, {}",
+                other, MIME_PNG, MIME_JPEG, MIME_WEBP, MIME_SVG
+            )),
+        }
+    }
+
+    /// Determine the format from the leading bytes rather than trusting a declared MIME type
+    fn sniff(bytes: &[u8]) -> Result<Self, String> {
+        match imagesize::image_type(bytes) {
+            Ok(ImageType::Png) => Ok(IconFormat::Png),
+            Ok(ImageType::Jpeg) => Ok(IconFormat::Jpeg),
+            Ok(ImageType::Webp) => Ok(IconFormat::Webp),
+            Ok(other) => Err(format!("Icon image format {:?} is not allowed", other)),
+            Err(_) => match std::str::from_utf8(bytes) {
+                Ok(text) if text.to_lowercase().contains("<svg") => Ok(IconFormat::Svg),
+                _ => Err("Icon bytes are not a recognized image format".to_string()),
+            },
+        }
+    }
+}
+
+/// Split a dataURL (eg. `data:image/png;base64,iVBORw0...`) into its MIME type and decoded bytes
+pub fn parse_data_url(src: &str) -> Result<(String, Vec<u8>), String> {
+    let (header, payload) = src
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or("Icon is not a valid dataURL".to_string())?;
+
+    let mut params = header.split(';');
+    let mime_type = params.next().unwrap_or_default().trim().to_lowercase();
+    let is_base64 = params.any(|param| param.trim() == "base64");
+
+    let bytes = if is_base64 {
+        STANDARD
+            .decode(payload.trim())
+            .map_err(|e| format!("Icon dataURL has invalid base64 content: {}", e))?
+    } else {
+        percent_decode(payload)?
+    };
+
+    Ok((mime_type, bytes))
+}
+
+fn percent_decode(payload: &str) -> Result<Vec<u8>, String> {
+    let raw = payload.as_bytes();
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut index = 0;
+
+    while index < raw.len() {
+        if raw[index] == b'%' {
+            let hex = raw
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or("Icon dataURL has an invalid percent-encoding".to_string())?;
+            bytes.push(hex);
+            index += 3;
+        } else {
+            bytes.push(raw[index]);
+            index += 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Validate an inline dataURL icon
+pub fn validate_data_url(src: &str) -> Result<(), String> {
+    let (mime_type, bytes) = parse_data_url(src)?;
+    let declared = IconFormat::from_mime_type(&mime_type)?;
+
+    validate_icon_bytes(Some(declared), &bytes)
+}
+
+/// Validate raw icon bytes, checking them against the declared format when there is one
+pub fn validate_icon_bytes(declared: Option<IconFormat>, bytes: &[u8]) -> Result<(), String> {
+    let format = IconFormat::sniff(bytes)?;
+
+    if let Some(declared) = declared {
+        if declared != format {
+            return Err(format!(
+                "Icon is declared as {:?} but its content is {:?}",
+                declared, format
+            ));
+        }
+    }
+
+    match format {
+        IconFormat::Svg => validate_svg(bytes),
+        _ => {
+            let size = imagesize::blob_size(bytes)
+                .map_err(|e| format!("Failed to read icon dimensions: {}", e))?;
+
+            if size.width > ICON_DIMENSION_LIMIT || size.height > ICON_DIMENSION_LIMIT {
+                Err(format!(
+                    "Icon may not exceed {} x {} pixels; found {} x {}",
+                    ICON_DIMENSION_LIMIT, ICON_DIMENSION_LIMIT, size.width, size.height
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn validate_svg(bytes: &[u8]) -> Result<(), String> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| "SVG icon is not valid UTF-8".to_string())?
+        .to_lowercase();
+    let text = decode_character_references(&text);
+
+    // Browsers ignore whitespace and control characters inside a URL scheme (eg. `java\tscript:`)
+    let compact: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+
+    for forbidden in ["<script", "<foreignobject"] {
+        if text.contains(forbidden) {
+            return Err(format!("SVG icon may not contain '{}'", forbidden));
+        }
+    }
+
+    // Internal entities can spell out anything the checks below look for (eg. `&j;script:`)
+    for forbidden in ["<!doctype", "<!entity"] {
+        if compact.contains(forbidden) {
+            return Err(format!("SVG icon may not contain '{}'", forbidden));
+        }
+    }
+
+    if compact.contains("javascript:") {
+        return Err("SVG icon may not contain 'javascript:'".to_string());
+    }
+
+    if has_event_handler_attribute(&text) {
+        return Err("SVG icon may not contain event handler attributes".to_string());
+    }
+
+    validate_svg_dimensions(&text)
+}
+
+/// Bound the size an SVG renders at, like the pixel limit for raster icons
+///
+/// `width` and `height` must be plain pixel lengths; where one is missing the `viewBox` size is
+/// used instead, so an icon must declare one or the other.
+fn validate_svg_dimensions(text: &str) -> Result<(), String> {
+    let attributes = svg_root_attributes(text)?;
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim())
+    };
+
+    let view_box = match attribute("viewbox") {
+        Some(value) => {
+            let numbers = value
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|part| !part.is_empty())
+                .map(|part| part.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| format!("SVG icon has an invalid viewBox '{}'", value))?;
+
+            match numbers[..] {
+                [_, _, width, height] if width > 0.0 && height > 0.0 => Some((width, height)),
+                _ => return Err(format!("SVG icon has an invalid viewBox '{}'", value)),
+            }
+        }
+        None => None,
+    };
+
+    for (name, view_box_length) in [
+        ("width", view_box.map(|(width, _)| width)),
+        ("height", view_box.map(|(_, height)| height)),
+    ] {
+        let length = match attribute(name) {
+            Some(value) => value
+                .strip_suffix("px")
+                .unwrap_or(value)
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|length| length.is_finite() && *length > 0.0)
+                .ok_or(format!(
+                    "SVG icon {} '{}' must be a length in pixels",
+                    name, value
+                ))?,
+            None => {
+                view_box_length.ok_or(format!("SVG icon must declare its {} or a viewBox", name))?
+            }
+        };
+
+        if length > ICON_DIMENSION_LIMIT as f64 {
+            return Err(format!(
+                "SVG icon may not exceed {} x {} pixels; found a {} of {}",
+                ICON_DIMENSION_LIMIT, ICON_DIMENSION_LIMIT, name, length
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The attributes of the root `<svg>` element as lowercased (name, value) pairs
+fn svg_root_attributes(text: &str) -> Result<Vec<(String, String)>, String> {
+    let start = text
+        .find("<svg")
+        .ok_or("SVG icon has no <svg> element".to_string())?;
+    let chars: Vec<char> = text[start + 4..].chars().collect();
+    let mut attributes = vec![];
+    let mut index = 0;
+
+    loop {
+        while index < chars.len() && (chars[index].is_whitespace() || chars[index] == '/') {
+            index += 1;
+        }
+        if index >= chars.len() || chars[index] == '>' {
+            break;
+        }
+
+        let name_start = index;
+        while index < chars.len()
+            && !chars[index].is_whitespace()
+            && !matches!(chars[index], '=' | '>' | '/')
+        {
+            index += 1;
+        }
+        let name: String = chars[name_start..index].iter().collect();
+
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+        if index >= chars.len() || chars[index] != '=' {
+            attributes.push((name, String::new()));
+            continue;
+        }
+        index += 1;
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+
+        let value: String = match chars.get(index) {
+            Some(quote @ ('"' | '\'')) => {
+                let value_start = index + 1;
+                let value_end = chars[value_start..]
+                    .iter()
+                    .position(|c| c == quote)
+                    .map(|offset| value_start + offset)
+                    .ok_or("SVG icon has an unterminated attribute value".to_string())?;
+                index = value_end + 1;
+                chars[value_start..value_end].iter().collect()
+            }
+            _ => {
+                let value_start = index;
+                while index < chars.len() && !chars[index].is_whitespace() && chars[index] != '>' {
+                    index += 1;
+                }
+                chars[value_start..index].iter().collect()
+            }
+        };
+        attributes.push((name, value));
+    }
+
+    Ok(attributes)
+}
+
+/// Named references that can be used to spell out a URL scheme or attribute without its literal
+/// characters; expects lowercased input
+const NAMED_REFERENCES: [(&'static str, char); 11] = [
+    ("&colon;", ':'),
+    ("&tab;", '\t'),
+    ("&newline;", '\n'),
+    ("&sol;", '/'),
+    ("&lpar;", '('),
+    ("&rpar;", ')'),
+    ("&equals;", '='),
+    ("&quot;", '"'),
+    ("&apos;", '\''),
+    ("&lt;", '<'),
+    ("&gt;", '>'),
+];
+
+/// Decode numeric (`&#58;`, `&#x3a;`) and scheme-related named character references so the checks
+/// above see the same text a browser would
+fn decode_character_references(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let (character, consumed) = parse_character_reference(rest).unwrap_or(('&', 1));
+        decoded.push(character);
+        rest = &rest[consumed..];
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Parse the character reference at the start of `text`, returning it with its length in bytes
+fn parse_character_reference(text: &str) -> Option<(char, usize)> {
+    if let Some(number) = text.strip_prefix("&#") {
+        let (digits, radix, prefix_length) = match number.strip_prefix('x') {
+            Some(digits) => (digits, 16, 3),
+            None => (number, 10, 2),
+        };
+        let length = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        let value = u32::from_str_radix(&digits[..length], radix).ok()?;
+        // The trailing semicolon is optional for numeric references
+        let terminated = digits[length..].starts_with(';');
+
+        Some((
+            char::from_u32(value).unwrap_or('\u{fffd}'),
+            prefix_length + length + terminated as usize,
+        ))
+    } else {
+        NAMED_REFERENCES
+            .iter()
+            .find(|(name, _)| text.starts_with(name))
+            .map(|(name, character)| (*character, name.len()))
+    }
+}
+
+/// Look for attributes such as `onload=` or `onclick =` which can execute scripts
+///
+/// Attribute names can follow whitespace, a `/` (`<svg/onload=...>`) or the closing quote of the
+/// previous attribute value (`x="1"onload=...`).
+fn has_event_handler_attribute(text: &str) -> bool {
+    let bytes = text.as_bytes();
+
+    text.match_indices("on").any(|(index, _)| {
+        let preceded_by_separator = index > 0
+            && (bytes[index - 1].is_ascii_whitespace()
+                || matches!(bytes[index - 1], b'/' | b'"' | b'\''));
+        let name_end = bytes[index + 2..]
+            .iter()
+            .position(|b| !b.is_ascii_alphabetic())
+            .map(|offset| index + 2 + offset)
+            .unwrap_or(bytes.len());
+        let value_start = bytes[name_end..]
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .map(|offset| name_end + offset);
+
+        preceded_by_separator
+            && name_end > index + 2
+            && value_start.map_or(false, |start| bytes[start] == b'=')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The signature and IHDR chunk of a PNG, which is all that is needed to read its dimensions
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        bytes.extend(13u32.to_be_bytes());
+        bytes.extend(b"IHDR");
+        bytes.extend(width.to_be_bytes());
+        bytes.extend(height.to_be_bytes());
+        bytes.extend([8, 6, 0, 0, 0]);
+        bytes.extend([0; 4]); // CRC is not checked
+        bytes
+    }
+
+    fn svg_result(svg: &str) -> Result<(), String> {
+        validate_icon_bytes(Some(IconFormat::Svg), svg.as_bytes())
+    }
+
+    #[test]
+    fn parse_data_url_base64() {
+        let src = format!(
+            "data:image/PNG;base64,{}",
+            STANDARD.encode(png_header(1, 1))
+        );
+
+        assert_eq!(
+            parse_data_url(&src),
+            Ok(("image/png".to_string(), png_header(1, 1)))
+        );
+    }
+
+    #[test]
+    fn parse_data_url_percent_encoded() {
+        assert_eq!(
+            parse_data_url("data:image/svg+xml,%3Csvg%3E%3C/svg%3E"),
+            Ok(("image/svg+xml".to_string(), b"<svg></svg>".to_vec()))
+        );
+        assert!(parse_data_url("data:image/svg+xml,%3").is_err());
+        assert!(parse_data_url("data:image/svg+xml,%zz").is_err());
+    }
+
+    #[test]
+    fn parse_data_url_invalid() {
+        assert!(parse_data_url("image/png;base64,AAAA").is_err());
+        assert!(parse_data_url("data:image/png;base64").is_err());
+        assert!(parse_data_url("data:image/png;base64,not base64!").is_err());
+    }
+
+    #[test]
+    fn validate_icon_bytes_png() {
+        assert_eq!(validate_icon_bytes(None, &png_header(300, 300)), Ok(()));
+        assert_eq!(
+            validate_icon_bytes(Some(IconFormat::Png), &png_header(64, 32)),
+            Ok(())
+        );
+        assert!(validate_icon_bytes(None, &png_header(301, 300)).is_err());
+        assert!(validate_icon_bytes(None, &png_header(300, 1_000)).is_err());
+    }
+
+    #[test]
+    fn validate_icon_bytes_format_mismatch() {
+        assert!(validate_icon_bytes(Some(IconFormat::Jpeg), &png_header(1, 1)).is_err());
+        assert!(validate_icon_bytes(Some(IconFormat::Png), b"<svg></svg>").is_err());
+        assert!(validate_icon_bytes(None, b"GIF89a\x01\x00\x01\x00").is_err());
+        assert!(validate_icon_bytes(None, b"plain text").is_err());
+    }
+
+    #[test]
+    fn validate_data_url_mime_type() {
+        let png = STANDARD.encode(png_header(1, 1));
+
+        assert_eq!(
+            validate_data_url(&format!("data:image/png;base64,{}", png)),
+            Ok(())
+        );
+        assert!(validate_data_url(&format!("data:image/gif;base64,{}", png)).is_err());
+    }
+
+    #[test]
+    fn validate_svg_allows_plain_markup() {
+        assert_eq!(
+            svg_result(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><path d="M0 0h10v10H0z" fill="red"/><text>Contact &amp; support</text></svg>"#
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_svg_rejects_scripts() {
+        assert!(svg_result("<svg><script>alert(1)</script></svg>").is_err());
+        assert!(svg_result("<svg><foreignObject></foreignObject></svg>").is_err());
+        assert!(svg_result(r#"<svg><a href="javascript:alert(1)"/></svg>"#).is_err());
+    }
+
+    #[test]
+    fn validate_svg_rejects_event_handlers() {
+        assert!(svg_result("<svg onload=alert(1)>").is_err());
+        assert!(svg_result("<svg\n  ONCLICK = 'alert(1)'>").is_err());
+        assert!(svg_result("<svg/onload=alert(1)>").is_err());
+        assert!(svg_result(r#"<svg x="1"onload="alert(1)">"#).is_err());
+        assert!(svg_result("<svg x='1'onload='alert(1)'>").is_err());
+    }
+
+    #[test]
+    fn validate_svg_rejects_entity_declarations() {
+        assert!(svg_result(
+            r#"<!DOCTYPE svg [<!ENTITY j "java">]><svg width="10" height="10"><a href="&j;script:alert(1)"/></svg>"#
+        )
+        .is_err());
+        assert!(svg_result(
+            r#"<?xml version="1.0"?><!doctype svg><svg width="10" height="10"></svg>"#
+        )
+        .is_err());
+        assert!(svg_result(r#"<svg width="10" height="10"><!EnTiTy x "y"></svg>"#).is_err());
+    }
+
+    #[test]
+    fn validate_svg_dimensions() {
+        assert_eq!(
+            svg_result(r#"<svg width="300" height="300px"></svg>"#),
+            Ok(())
+        );
+        assert_eq!(
+            svg_result(r#"<svg viewBox="0,0 , 64 32"><path d="M0 0"/></svg>"#),
+            Ok(())
+        );
+        assert_eq!(
+            svg_result(r#"<svg viewBox="0 0 4096 4096" width="48" height="48"></svg>"#),
+            Ok(())
+        );
+
+        assert!(svg_result("<svg></svg>").is_err());
+        assert!(svg_result(r#"<svg width="301" height="10"></svg>"#).is_err());
+        assert!(svg_result(r#"<svg width='10' height=100000></svg>"#).is_err());
+        assert!(svg_result(r#"<svg viewBox="0 0 10 10" width="100%"></svg>"#).is_err());
+        assert!(svg_result(r#"<svg viewBox="0 0 10000 10" height="10"></svg>"#).is_err());
+        assert!(svg_result(r#"<svg viewBox="0 0 -1 10"></svg>"#).is_err());
+        assert!(svg_result(r#"<svg viewBox="0 0 10"></svg>"#).is_err());
+        assert!(svg_result(r#"<svg width="10" height="10" viewBox="0 0 10 10"#).is_err());
+    }
+
+    #[test]
+    fn validate_svg_rejects_encoded_schemes() {
+        assert!(svg_result(r#"<svg><a href="javascript&#58;alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="javascript&#x3a;alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="javascript&#X3A alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="javascript&#0000058alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="javascript&colon;alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="&#106;avascript:alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="java&#x09;script:alert(1)"/></svg>"#).is_err());
+        assert!(svg_result(r#"<svg><a href="java&Tab;script:alert(1)"/></svg>"#).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 mod errors;
+mod icons;
 mod validation;
 
 use essence::EssenceResponse;
@@ -7,11 +8,20 @@ use hdi::prelude::*;
 use serde::de::{Deserializer, Error};
 
 pub use appstore_types::{
-    AppEntry, CommonFields, DeprecationNotice, EntityId, LocationTriplet, PublisherEntry,
-    WebAddress, WebHappConfig,
+    is_valid_locale_tag, parse_verification_proof, parse_version, validate_role_manifests,
+    verification_challenge, verification_proof, website_domain, AdvisoryEntry, AdvisorySeverity,
+    AppDraftEntry, AppEntry, ChannelPreferenceEntry, CollectionEntry, CommentEntry, CommonFields,
+    DelistingEntry, DelistingTarget, DeprecationNotice, DnaProperties, EndorsementEntry, EntityId,
+    FavoriteEntry, LocalizedText, LocationTriplet, PendingRelease, ProvisioningStrategy,
+    PublisherEntry, ReleaseChannel, ReleaseEntry, ReleaseHashes, ReportEntry, ReportReason,
+    ReportTarget, ReviewEntry, ReviewResponseEntry, RoleManifest, RoleProvisioning,
+    ScheduledPublicationEntry, ScheduledTarget, Screenshot, VerificationEntry, WebAddress,
+    WebHappConfig, YankNotice, VERIFICATION_WELL_KNOWN_PATH,
 };
+pub use mere_memory_types::{CompressionCodec, MemoryBlockEntry, MemoryEntry, SequencePosition};
 
 pub use errors::{AppError, ErrorKinds, UserError};
+pub use validation::validate_app_fields;
 pub type AppResult<T> = Result<T, ErrorKinds>;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +41,24 @@ pub fn composition<T>(payload: T, composition: &str) -> Response<T> {
     )
 }
 
+/// Read the typed DNA properties, treating `properties: ~` as no properties
+pub fn dna_properties() -> ExternResult<DnaProperties> {
+    let properties: Option<DnaProperties> = decode(dna_info()?.modifiers.properties.bytes())
+        .map_err(|e| wasm_error!(WasmErrorInner::from(e)))?;
+
+    Ok(properties.unwrap_or_default())
+}
+
+pub fn get_stewards() -> ExternResult<Vec<AgentPubKey>> {
+    dna_properties()?
+        .steward_keys()
+        .map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))
+}
+
+pub fn is_steward(agent: &AgentPubKey) -> ExternResult<bool> {
+    Ok(get_stewards()?.contains(agent))
+}
+
 #[hdk_entry_defs]
 #[unit_enum(EntryTypesUnit)]
 pub enum EntryTypes {
@@ -38,10 +66,52 @@ pub enum EntryTypes {
     Publisher(PublisherEntry),
     #[entry_def]
     App(AppEntry),
+    #[entry_def]
+    Memory(MemoryEntry),
+    #[entry_def]
+    MemoryBlock(MemoryBlockEntry),
+    #[entry_def]
+    Review(ReviewEntry),
+    #[entry_def]
+    ReviewResponse(ReviewResponseEntry),
+    #[entry_def]
+    Report(ReportEntry),
+    #[entry_def]
+    Release(ReleaseEntry),
+    #[entry_def]
+    Delisting(DelistingEntry),
+    #[entry_def]
+    Collection(CollectionEntry),
+    #[entry_def]
+    Endorsement(EndorsementEntry),
+    #[entry_def]
+    Verification(VerificationEntry),
+    #[entry_def]
+    Comment(CommentEntry),
+    #[entry_def]
+    Advisory(AdvisoryEntry),
+    #[entry_def(visibility = "private")]
+    Favorite(FavoriteEntry),
+    #[entry_def(visibility = "private")]
+    AppDraft(AppDraftEntry),
+    #[entry_def(visibility = "private")]
+    ScheduledPublication(ScheduledPublicationEntry),
+    #[entry_def(visibility = "private")]
+    ChannelPreference(ChannelPreferenceEntry),
 }
 
 entry_model!(EntryTypes::Publisher(PublisherEntry));
 entry_model!(EntryTypes::App(AppEntry));
+entry_model!(EntryTypes::Review(ReviewEntry));
+entry_model!(EntryTypes::ReviewResponse(ReviewResponseEntry));
+entry_model!(EntryTypes::Report(ReportEntry));
+entry_model!(EntryTypes::Release(ReleaseEntry));
+entry_model!(EntryTypes::Delisting(DelistingEntry));
+entry_model!(EntryTypes::Collection(CollectionEntry));
+entry_model!(EntryTypes::Endorsement(EndorsementEntry));
+entry_model!(EntryTypes::Verification(VerificationEntry));
+entry_model!(EntryTypes::Comment(CommentEntry));
+entry_model!(EntryTypes::Advisory(AdvisoryEntry));
 
 #[hdk_link_types]
 pub enum LinkTypes {
@@ -49,6 +119,17 @@ pub enum LinkTypes {
 
     Publisher,
     App,
+    Review,
+    ReviewResponse,
+    Report,
+    Release,
+    Delisting,
+    Collection,
+    Endorsement,
+    Verification,
+    Install,
+    Comment,
+    Advisory,
 
     Anchor,
 }
@@ -64,6 +145,17 @@ impl<'de> Deserialize<'de> for LinkTypes {
 
             "Publisher" => Ok(LinkTypes::Publisher),
             "App" => Ok(LinkTypes::App),
+            "Review" => Ok(LinkTypes::Review),
+            "ReviewResponse" => Ok(LinkTypes::ReviewResponse),
+            "Report" => Ok(LinkTypes::Report),
+            "Release" => Ok(LinkTypes::Release),
+            "Delisting" => Ok(LinkTypes::Delisting),
+            "Collection" => Ok(LinkTypes::Collection),
+            "Endorsement" => Ok(LinkTypes::Endorsement),
+            "Verification" => Ok(LinkTypes::Verification),
+            "Install" => Ok(LinkTypes::Install),
+            "Comment" => Ok(LinkTypes::Comment),
+            "Advisory" => Ok(LinkTypes::Advisory),
 
             "Anchor" => Ok(LinkTypes::Anchor),
 
@@ -1,15 +1,38 @@
+use crate::icons;
 use crate::{
-    AppEntry,
-    CommonFields,
-
-    EntryTypes,
-    // LinkTypes,
-    PublisherEntry,
+    is_steward, is_valid_locale_tag, parse_version, validate_role_manifests,
+    verification_challenge, website_domain, AdvisoryEntry, AppDraftEntry, AppEntry,
+    ChannelPreferenceEntry, CollectionEntry, CommentEntry, CommonFields, DelistingEntry,
+    DelistingTarget, EndorsementEntry, EntryTypes, EntryTypesUnit, FavoriteEntry, LinkTypes,
+    MemoryBlockEntry, MemoryEntry, PublisherEntry, ReleaseEntry, ReportEntry, ReportTarget,
+    ReviewEntry, ReviewResponseEntry, ScheduledPublicationEntry, Screenshot, VerificationEntry,
 };
 use hdi::prelude::*;
-pub use mere_memory_types::MemoryEntry;
 
 const ICON_SIZE_LIMIT: usize = 400_000; // Maximum characters for a dataURL of a 300 x 300 pixel image is around 360'000
+const ICON_BYTES_LIMIT: u64 = 300_000; // Decoded equivalent of ICON_SIZE_LIMIT
+const MEMORY_COMPRESSION_RATIO_LIMIT: u64 = 100; // Far above what real bundles reach; bounds decompression work
+const SCREENSHOT_COUNT_LIMIT: usize = 10;
+const SCREENSHOT_BYTES_LIMIT: u64 = 2_000_000;
+const SCREENSHOT_CAPTION_LIMIT: usize = 280;
+const REVIEW_TEXT_LIMIT: usize = 5_000;
+const COMMENT_TEXT_LIMIT: usize = 5_000;
+const ADVISORY_DESCRIPTION_LIMIT: usize = 10_000;
+const COLLECTION_TITLE_LIMIT: usize = 100;
+const COLLECTION_APPS_LIMIT: usize = 200;
+const YANK_REASON_LIMIT: usize = 5_000;
+const REVISION_DEPTH_LIMIT: usize = 1_000; // Maximum updates walked back to find an entity's create
+const DELISTING_REASON_LIMIT: usize = 5_000;
+const REPORT_DETAILS_LIMIT: usize = 5_000;
+const REPORT_RATE_LIMIT: usize = 5; // Maximum reports per agent within REPORT_RATE_WINDOW
+const REPORT_RATE_WINDOW: i64 = 24 * 60 * 60 * 1_000_000; // 24 hours in microseconds
+
+// Path anchors that links are validated against; these must match the coordinator's constants
+const ANCHOR_AGENTS: &str = "agents";
+const ANCHOR_APPS: &str = "apps";
+const ANCHOR_PUBLISHERS: &str = "publishers";
+const ANCHOR_ENDORSEMENTS: &str = "endorsements";
+const ANCHOR_INSTALLS: &str = "installs";
 
 #[hdk_extern]
 fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
@@ -31,6 +54,70 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         ActionType::Create => validate_app_create(&op, content),
                         _ => Ok(ValidateCallbackResult::Valid),
                     },
+                    EntryTypes::Memory(content) => match op.action_type() {
+                        ActionType::Create => validate_memory_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::MemoryBlock(content) => match op.action_type() {
+                        ActionType::Create => validate_memory_block_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Review(content) => match op.action_type() {
+                        ActionType::Create => validate_review_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::ReviewResponse(content) => match op.action_type() {
+                        ActionType::Create => validate_review_response_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Report(content) => match op.action_type() {
+                        ActionType::Create => validate_report_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Release(content) => match op.action_type() {
+                        ActionType::Create => validate_release_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Delisting(content) => match op.action_type() {
+                        ActionType::Create => validate_delisting_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Collection(content) => match op.action_type() {
+                        ActionType::Create => validate_collection_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Endorsement(content) => match op.action_type() {
+                        ActionType::Create => validate_endorsement_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Verification(content) => match op.action_type() {
+                        ActionType::Create => validate_verification_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Favorite(content) => match op.action_type() {
+                        ActionType::Create => validate_favorite_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::AppDraft(content) => match op.action_type() {
+                        ActionType::Create => validate_app_draft_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::ScheduledPublication(content) => match op.action_type() {
+                        ActionType::Create => validate_scheduled_publication_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::ChannelPreference(content) => match op.action_type() {
+                        ActionType::Create => validate_channel_preference_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Comment(content) => match op.action_type() {
+                        ActionType::Create => validate_comment_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
+                    EntryTypes::Advisory(content) => match op.action_type() {
+                        ActionType::Create => validate_advisory_create(&op, content),
+                        _ => Ok(ValidateCallbackResult::Valid),
+                    },
                     // _ => Ok(ValidateCallbackResult::Valid),
                 };
             } else {
@@ -58,6 +145,81 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         let original_entry: AppEntry =
                             register_update.original_entry.unwrap().try_into()?;
                         validate_app_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Memory(_) | EntryTypes::MemoryBlock(_) => {
+                        Ok(ValidateCallbackResult::Invalid(
+                            "Memory entries cannot be updated".to_string(),
+                        ))
+                    }
+                    EntryTypes::Review(content) => {
+                        let original_entry: ReviewEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_review_update(&op, content, original_entry)
+                    }
+                    EntryTypes::ReviewResponse(content) => {
+                        let original_entry: ReviewResponseEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_review_response_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Report(content) => {
+                        let original_entry: ReportEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_report_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Release(content) => {
+                        let original_entry: ReleaseEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_release_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Delisting(content) => {
+                        let original_entry: DelistingEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_delisting_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Collection(content) => {
+                        let original_entry: CollectionEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_collection_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Endorsement(content) => {
+                        let original_entry: EndorsementEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_endorsement_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Verification(content) => {
+                        let original_entry: VerificationEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_verification_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Favorite(content) => {
+                        let original_entry: FavoriteEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_favorite_update(&op, content, original_entry)
+                    }
+                    EntryTypes::AppDraft(content) => {
+                        let original_entry: AppDraftEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_app_draft_update(&op, content, original_entry)
+                    }
+                    EntryTypes::ScheduledPublication(content) => {
+                        let original_entry: ScheduledPublicationEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_scheduled_publication_update(&op, content, original_entry)
+                    }
+                    EntryTypes::ChannelPreference(content) => {
+                        let original_entry: ChannelPreferenceEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_channel_preference_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Comment(content) => {
+                        let original_entry: CommentEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_comment_update(&op, content, original_entry)
+                    }
+                    EntryTypes::Advisory(content) => {
+                        let original_entry: AdvisoryEntry =
+                            register_update.original_entry.unwrap().try_into()?;
+                        validate_advisory_update(&op, content, original_entry)
                     } // _ => Ok(ValidateCallbackResult::Valid),
                 };
             }
@@ -76,7 +238,82 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         validate_publisher_delete(&op, original_entry)
                     }
                     EntryTypes::App(original_entry) => validate_app_delete(&op, original_entry),
-                    // _ => Ok(ValidateCallbackResult::Valid),
+                    EntryTypes::Memory(_) | EntryTypes::MemoryBlock(_) => {
+                        Ok(ValidateCallbackResult::Valid)
+                    }
+                    EntryTypes::Review(original_entry) => {
+                        validate_review_delete(&op, original_entry)
+                    }
+                    EntryTypes::ReviewResponse(original_entry) => {
+                        validate_review_response_delete(&op, original_entry)
+                    }
+                    EntryTypes::Report(original_entry) => {
+                        validate_report_delete(&op, original_entry)
+                    }
+                    EntryTypes::Release(original_entry) => {
+                        validate_release_delete(&op, original_entry)
+                    }
+                    EntryTypes::Delisting(original_entry) => {
+                        validate_delisting_delete(&op, original_entry)
+                    }
+                    EntryTypes::Collection(original_entry) => {
+                        validate_collection_delete(&op, original_entry)
+                    }
+                    EntryTypes::Endorsement(original_entry) => {
+                        validate_endorsement_delete(&op, original_entry)
+                    }
+                    EntryTypes::Verification(original_entry) => {
+                        validate_verification_delete(&op, original_entry)
+                    }
+                    EntryTypes::Favorite(original_entry) => {
+                        validate_favorite_delete(&op, original_entry)
+                    }
+                    EntryTypes::AppDraft(original_entry) => {
+                        validate_app_draft_delete(&op, original_entry)
+                    }
+                    EntryTypes::ScheduledPublication(original_entry) => {
+                        validate_scheduled_publication_delete(&op, original_entry)
+                    }
+                    EntryTypes::ChannelPreference(original_entry) => {
+                        validate_channel_preference_delete(&op, original_entry)
+                    }
+                    EntryTypes::Comment(original_entry) => {
+                        validate_comment_delete(&op, original_entry)
+                    }
+                    EntryTypes::Advisory(original_entry) => {
+                        validate_advisory_delete(&op, original_entry)
+                    } // _ => Ok(ValidateCallbackResult::Valid),
+                };
+            }
+        }
+
+        // When creating a link
+        Op::RegisterCreateLink(register_create_link) => {
+            let create_link = register_create_link.create_link.hashed.content;
+
+            if let Some(link_type) =
+                LinkTypes::from_type(create_link.zome_index, create_link.link_type)?
+            {
+                return match link_type {
+                    LinkTypes::Endorsement => validate_endorsement_link_create(&op, create_link),
+                    LinkTypes::Install => validate_install_link_create(&op, create_link),
+                    _ => Ok(ValidateCallbackResult::Valid),
+                };
+            }
+        }
+
+        // When deleting a link
+        Op::RegisterDeleteLink(register_delete_link) => {
+            let create_link = register_delete_link.create_link;
+
+            if let Some(link_type) =
+                LinkTypes::from_type(create_link.zome_index, create_link.link_type)?
+            {
+                return match link_type {
+                    LinkTypes::Comment => validate_comment_link_delete(&op, create_link),
+                    LinkTypes::Endorsement => validate_endorsement_link_delete(&op, create_link),
+                    LinkTypes::Install => validate_install_link_delete(&op, create_link),
+                    _ => Ok(ValidateCallbackResult::Valid),
                 };
             }
         }
@@ -84,8 +321,6 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
         // Ignore the rest
         //  - StoreRecord
         //  - RegisterAgentActivity
-        //  - RegisterCreateLink
-        //  - RegisterDeleteLink
         _ => {
             debug!("Op::{} => No validation handler", op.action_type());
             return Ok(ValidateCallbackResult::Valid);
@@ -143,6 +378,195 @@ where
     }
 }
 
+/// Icons are either referenced by MemoryEntry address or, for legacy entries, inlined as a dataURL
+fn validate_icon_fields(
+    entry_type: &str,
+    icon_src: &String,
+    icon: &Option<EntryHash>,
+) -> ExternResult<ValidateCallbackResult> {
+    let result = if let Some(address) = icon {
+        if !icon_src.is_empty() {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "{} cannot have both an icon address and an inline icon_src",
+                entry_type
+            )));
+        }
+
+        let memory: MemoryEntry = must_get_entry(address.to_owned())?.content.try_into()?;
+
+        if memory.memory_size > ICON_BYTES_LIMIT {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "{} icon may not exceed {} bytes",
+                entry_type, ICON_BYTES_LIMIT
+            )));
+        }
+
+        let mut bytes = vec![];
+        for block_address in memory.block_addresses.iter() {
+            let block: MemoryBlockEntry = must_get_entry(block_address.to_owned())?
+                .content
+                .try_into()?;
+            bytes.extend(block.bytes);
+        }
+
+        // memory_size is at most ICON_BYTES_LIMIT, and decoding stops once output exceeds it
+        memory
+            .decode_bytes(bytes)
+            .and_then(|bytes| icons::validate_icon_bytes(None, &bytes))
+    } else if icon_src.is_empty() {
+        Ok(())
+    } else if icon_src.chars().count() > ICON_SIZE_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "{} icon dataURL may not exceed {} characters",
+            entry_type, ICON_SIZE_LIMIT
+        )));
+    } else {
+        icons::validate_data_url(icon_src)
+    };
+
+    match result {
+        Ok(()) => Ok(ValidateCallbackResult::Valid),
+        Err(message) => Ok(ValidateCallbackResult::Invalid(format!(
+            "{} has an invalid icon: {}",
+            entry_type, message
+        ))),
+    }
+}
+
+/// Collect the entries of the given type that were created on the author's chain before the action
+/// being validated; when `live_only` is set, entries that have since been deleted are skipped
+fn get_chain_entries<T>(
+    op: &Op,
+    entry_type: EntryTypesUnit,
+    live_only: bool,
+) -> ExternResult<Vec<(ActionHash, Timestamp, T)>>
+where
+    T: TryFrom<Entry, Error = WasmError>,
+{
+    let chain_top = match op.prev_action() {
+        Some(prev_action) => prev_action.to_owned(),
+        None => return Ok(vec![]),
+    };
+    let scoped_type: ScopedEntryDefIndex = entry_type.try_into()?;
+    let activity = must_get_agent_activity(op.author().to_owned(), ChainFilter::new(chain_top))?;
+
+    let deleted: Vec<ActionHash> = activity
+        .iter()
+        .filter_map(|item| match &item.action.hashed.content {
+            Action::Delete(delete) => Some(delete.deletes_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let mut entries = vec![];
+    for item in activity.iter() {
+        if let Action::Create(create) = &item.action.hashed.content {
+            if let EntryType::App(AppEntryDef {
+                zome_index,
+                entry_index,
+                ..
+            }) = create.entry_type
+            {
+                if zome_index == scoped_type.zome_index
+                    && entry_index == scoped_type.zome_type
+                    && !(live_only && deleted.contains(&item.action.hashed.hash))
+                {
+                    let entry: T = must_get_entry(create.entry_hash.to_owned())?
+                        .content
+                        .try_into()?;
+                    entries.push((item.action.hashed.hash.to_owned(), create.timestamp, entry));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Get the links of the given type that the author created before this op
+fn get_chain_links(
+    op: &Op,
+    link_type: LinkTypes,
+    live_only: bool,
+) -> ExternResult<Vec<(ActionHash, CreateLink)>> {
+    let chain_top = match op.prev_action() {
+        Some(prev_action) => prev_action.to_owned(),
+        None => return Ok(vec![]),
+    };
+    let scoped_type: ScopedLinkType = link_type.try_into()?;
+    let activity = must_get_agent_activity(op.author().to_owned(), ChainFilter::new(chain_top))?;
+
+    let deleted: Vec<ActionHash> = activity
+        .iter()
+        .filter_map(|item| match &item.action.hashed.content {
+            Action::DeleteLink(delete_link) => Some(delete_link.link_add_address.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    Ok(activity
+        .into_iter()
+        .filter_map(|item| match item.action.hashed.content {
+            Action::CreateLink(create_link)
+                if create_link.zome_index == scoped_type.zome_index
+                    && create_link.link_type == scoped_type.zome_type
+                    && !(live_only && deleted.contains(&item.action.hashed.hash)) =>
+            {
+                Some((item.action.hashed.hash, create_link))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Deserialize the entry of a record that must already be valid
+///
+/// An entity id is the address of its create action, so this returns the entity as it was first
+/// created.  Later updates (eg. to `editors`) are not visible: a validator cannot deterministically
+/// find the latest update because updates are discovered through the DHT, not the entity's chain.
+fn must_get_valid_app_entry<T>(address: &ActionHash, entry_type: &str) -> ExternResult<T>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    must_get_valid_record(address.to_owned())?
+        .entry()
+        .to_app_option::<T>()
+        .map_err(|e| wasm_error!(WasmErrorInner::from(e)))?
+        .ok_or(hc_utils::guest_err(format!(
+            "Record {} is not a {}",
+            address, entry_type
+        )))
+}
+
+/// Deserialize a revision of an entity, where `revision` must be the entity's create action or an
+/// update that leads back to it
+fn must_get_valid_revision<T>(
+    id: &ActionHash,
+    revision: &ActionHash,
+    entry_type: &str,
+) -> ExternResult<T>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let mut address = revision.to_owned();
+
+    for _ in 0..REVISION_DEPTH_LIMIT {
+        if &address == id {
+            return must_get_valid_app_entry(revision, entry_type);
+        }
+
+        match must_get_action(address.to_owned())?.action() {
+            Action::Update(update) => address = update.original_action_address.to_owned(),
+            _ => break,
+        }
+    }
+
+    Err(hc_utils::guest_err(format!(
+        "Record {} is not a revision of {} {}",
+        revision, entry_type, id
+    )))
+}
+
 //
 // Publisher
 //
@@ -150,13 +574,7 @@ fn validate_common_publisher_fields(
     _op: &Op,
     entry: &PublisherEntry,
 ) -> ExternResult<ValidateCallbackResult> {
-    if entry.icon_src.chars().count() > ICON_SIZE_LIMIT {
-        Ok(ValidateCallbackResult::Invalid(format!(
-            "PublisherEntry may not exceed 300 x 300 pixels"
-        )))
-    } else {
-        Ok(ValidateCallbackResult::Valid)
-    }
+    validate_icon_fields("PublisherEntry", &entry.icon_src, &entry.icon)
 }
 
 fn validate_publisher_create(
@@ -211,13 +629,107 @@ fn validate_publisher_delete(
 // App
 //
 fn validate_common_app_fields(_op: &Op, entry: &AppEntry) -> ExternResult<ValidateCallbackResult> {
-    if entry.icon_src.chars().count() > ICON_SIZE_LIMIT {
-        Ok(ValidateCallbackResult::Invalid(format!(
-            "AppEntry icon may not exceed 300 x 300 pixels"
-        )))
-    } else {
-        Ok(ValidateCallbackResult::Valid)
+    validate_app_fields(entry)
+}
+
+/// The checks every `AppEntry` version must pass, so that coordinators can reject an app before
+/// anything is written
+pub fn validate_app_fields(entry: &AppEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_icon_fields("AppEntry", &entry.icon_src, &entry.icon)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_screenshots(&entry.screenshots)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    for (index, channel) in entry.channels.iter().enumerate() {
+        if entry.channels[..index].contains(channel) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "AppEntry channel {:?} is listed more than once",
+                channel
+            )));
+        }
+    }
+
+    validate_localizations(entry)
+}
+
+fn validate_localizations(entry: &AppEntry) -> ExternResult<ValidateCallbackResult> {
+    match &entry.default_locale {
+        Some(locale) if !is_valid_locale_tag(locale) => {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "AppEntry default_locale '{}' is not a valid BCP-47 tag",
+                locale
+            )));
+        }
+        None if !entry.localizations.is_empty() => {
+            return Ok(ValidateCallbackResult::Invalid(
+                "AppEntry with localizations must declare a default_locale".to_string(),
+            ));
+        }
+        _ => (),
+    }
+
+    for (locale, text) in entry.localizations.iter() {
+        if !is_valid_locale_tag(locale) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "AppEntry localization '{}' is not a valid BCP-47 tag",
+                locale
+            )));
+        }
+
+        if text.title.trim().is_empty() {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "AppEntry localization '{}' must have a title",
+                locale
+            )));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_screenshots(screenshots: &Vec<Screenshot>) -> ExternResult<ValidateCallbackResult> {
+    if screenshots.len() > SCREENSHOT_COUNT_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "AppEntry may not have more than {} screenshots",
+            SCREENSHOT_COUNT_LIMIT
+        )));
+    }
+
+    for screenshot in screenshots.iter() {
+        if let Some(locale) = &screenshot.locale {
+            if !is_valid_locale_tag(locale) {
+                return Ok(ValidateCallbackResult::Invalid(format!(
+                    "Screenshot locale '{}' is not a valid BCP-47 tag",
+                    locale
+                )));
+            }
+        }
+
+        if screenshot.caption.chars().count() > SCREENSHOT_CAPTION_LIMIT {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Screenshot caption may not exceed {} characters",
+                SCREENSHOT_CAPTION_LIMIT
+            )));
+        }
+
+        let memory: MemoryEntry = must_get_entry(screenshot.image.to_owned())?
+            .content
+            .try_into()?;
+
+        if memory.memory_size > SCREENSHOT_BYTES_LIMIT {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Screenshot {} may not exceed {} bytes",
+                screenshot.image, SCREENSHOT_BYTES_LIMIT
+            )));
+        }
     }
+
+    Ok(ValidateCallbackResult::Valid)
 }
 
 fn validate_app_create(op: &Op, entry: AppEntry) -> ExternResult<ValidateCallbackResult> {
@@ -259,3 +771,1398 @@ fn validate_app_update(
 fn validate_app_delete(_op: &Op, _entry: AppEntry) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
+
+//
+// Memory
+//
+/// Reads the bytes of a memory's blocks in order, fetching each block only when it is reached
+///
+/// Errors are kept aside so that a missing block stays an unresolved dependency and a block out of
+/// sequence is reported as such, rather than both becoming a read error.
+struct MemoryBlocks<'a> {
+    addresses: &'a Vec<EntryHash>,
+    next: usize,
+    bytes: Vec<u8>,
+    offset: usize,
+    fetch_error: Option<WasmError>,
+    sequence_error: Option<String>,
+}
+
+impl<'a> MemoryBlocks<'a> {
+    fn new(addresses: &'a Vec<EntryHash>) -> Self {
+        MemoryBlocks {
+            addresses,
+            next: 0,
+            bytes: vec![],
+            offset: 0,
+            fetch_error: None,
+            sequence_error: None,
+        }
+    }
+
+    fn fetch_next(&mut self) -> Result<(), String> {
+        let address = &self.addresses[self.next];
+        let block: MemoryBlockEntry = match must_get_entry(address.to_owned())
+            .and_then(|entry| entry.content.try_into())
+        {
+            Ok(block) => block,
+            Err(error) => {
+                let message = format!("Failed to get MemoryBlockEntry {}: {:?}", address, error);
+                self.fetch_error = Some(error);
+                return Err(message);
+            }
+        };
+        let block_count = self.addresses.len() as u64;
+
+        if block.sequence.position != self.next as u64 + 1 || block.sequence.length != block_count {
+            let message = format!(
+                "MemoryBlockEntry {} is out of sequence: expected {} of {}, found {} of {}",
+                address,
+                self.next + 1,
+                block_count,
+                block.sequence.position,
+                block.sequence.length
+            );
+            self.sequence_error = Some(message.clone());
+            return Err(message);
+        }
+
+        self.next += 1;
+        self.bytes = block.bytes;
+        self.offset = 0;
+
+        Ok(())
+    }
+}
+
+impl<'a> std::io::Read for MemoryBlocks<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.offset == self.bytes.len() {
+            if self.next == self.addresses.len() {
+                return Ok(0);
+            }
+            self.fetch_next()
+                .map_err(|message| std::io::Error::new(std::io::ErrorKind::Other, message))?;
+        }
+
+        let read = buf.len().min(self.bytes.len() - self.offset);
+        buf[..read].copy_from_slice(&self.bytes[self.offset..self.offset + read]);
+        self.offset += read;
+
+        Ok(read)
+    }
+}
+
+fn validate_memory_create(op: &Op, entry: MemoryEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Entry author does not match Action author: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    if entry.memory_size > 0 && entry.block_addresses.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "MemoryEntry with a size of {} bytes must have at least 1 block",
+            entry.memory_size
+        )));
+    }
+
+    // Blocks are fetched one at a time while the byte-set is hashed, so it is never reassembled
+    let mut blocks = MemoryBlocks::new(&entry.block_addresses);
+    let result = entry.verify_stream(&mut blocks, MEMORY_COMPRESSION_RATIO_LIMIT);
+
+    if let Some(error) = blocks.fetch_error {
+        return Err(error);
+    }
+    if let Some(message) = blocks.sequence_error {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+    if blocks.next != entry.block_addresses.len() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "MemoryEntry content ends before its last block ({} of {} read)",
+            blocks.next,
+            entry.block_addresses.len()
+        )));
+    }
+    if let Err(message) = result {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "MemoryEntry does not describe its blocks: {}",
+            message
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_memory_block_create(
+    _op: &Op,
+    entry: MemoryBlockEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.sequence.position < 1 || entry.sequence.position > entry.sequence.length {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "MemoryBlockEntry position {} is outside of sequence length {}",
+            entry.sequence.position, entry.sequence.length
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Review
+//
+fn validate_common_review_fields(
+    _op: &Op,
+    entry: &ReviewEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.rating < 1 || entry.rating > 5 {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReviewEntry rating must be between 1 and 5; found {}",
+            entry.rating
+        )));
+    }
+
+    if entry.text.chars().count() > REVIEW_TEXT_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReviewEntry text may not exceed {} characters",
+            REVIEW_TEXT_LIMIT
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Editors of the app revision that the review depends on cannot review it
+///
+/// The editors as first created are checked too, so that referencing the original revision does not
+/// let them through either.
+fn validate_review_author(entry: &ReviewEntry) -> ExternResult<ValidateCallbackResult> {
+    let original: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+    let revision: AppEntry = must_get_valid_revision(&entry.app, &entry.app_action, "AppEntry")?;
+
+    if original.editors.contains(&entry.author) || revision.editors.contains(&entry.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Editors cannot review their own app".to_string(),
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_review_create(op: &Op, entry: ReviewEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_review_fields(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_review_author(&entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    let previous_reviews: Vec<(ActionHash, Timestamp, ReviewEntry)> =
+        get_chain_entries(op, EntryTypesUnit::Review, true)?;
+
+    if previous_reviews
+        .iter()
+        .any(|(_, _, review)| review.app == entry.app)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} already has a review for app {}",
+            entry.author, entry.app
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_review_update(
+    op: &Op,
+    entry: ReviewEntry,
+    prev_entry: ReviewEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.app != prev_entry.app {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Cannot change review target: {} => {}",
+            prev_entry.app, entry.app
+        )));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_review_fields(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.app_action != prev_entry.app_action {
+        if let ValidateCallbackResult::Invalid(message) = validate_review_author(&entry)? {
+            return Ok(ValidateCallbackResult::Invalid(message));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_review_delete(op: &Op, entry: ReviewEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() && !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the review author or a steward can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Review Response
+//
+fn validate_review_response_create(
+    op: &Op,
+    entry: ReviewResponseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.text.chars().count() > REVIEW_TEXT_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReviewResponseEntry text may not exceed {} characters",
+            REVIEW_TEXT_LIMIT
+        )));
+    }
+
+    validate_review_response_author(&entry)
+}
+
+/// The responder must be an editor of the referenced revision of the reviewed app's publisher
+fn validate_review_response_author(
+    entry: &ReviewResponseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    let review: ReviewEntry = must_get_valid_app_entry(&entry.review, "ReviewEntry")?;
+    let app: AppEntry = must_get_valid_app_entry(&review.app, "AppEntry")?;
+    let publisher: PublisherEntry =
+        must_get_valid_revision(&app.publisher, &entry.publisher_action, "PublisherEntry")?;
+
+    if publisher.author != entry.author && !publisher.editors.contains(&entry.author) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} is not an editor of publisher {}",
+            entry.author, app.publisher
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_review_response_update(
+    op: &Op,
+    entry: ReviewResponseEntry,
+    prev_entry: ReviewResponseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.review != prev_entry.review {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Cannot change response target: {} => {}",
+            prev_entry.review, entry.review
+        )));
+    }
+
+    if entry.text.chars().count() > REVIEW_TEXT_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReviewResponseEntry text may not exceed {} characters",
+            REVIEW_TEXT_LIMIT
+        )));
+    }
+
+    if entry.publisher_action != prev_entry.publisher_action {
+        return validate_review_response_author(&entry);
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_review_response_delete(
+    op: &Op,
+    entry: ReviewResponseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() && !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the response author or a steward can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Report
+//
+fn validate_report_create(op: &Op, entry: ReportEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.details.chars().count() > REPORT_DETAILS_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReportEntry details may not exceed {} characters",
+            REPORT_DETAILS_LIMIT
+        )));
+    }
+
+    match &entry.target {
+        ReportTarget::App(id) => {
+            let _: AppEntry = must_get_valid_app_entry(id, "AppEntry")?;
+        }
+        ReportTarget::Publisher(id) => {
+            let _: PublisherEntry = must_get_valid_app_entry(id, "PublisherEntry")?;
+        }
+    }
+
+    // Deleted reports still count towards the limit
+    let previous_reports: Vec<(ActionHash, Timestamp, ReportEntry)> =
+        get_chain_entries(op, EntryTypesUnit::Report, false)?;
+    let window_start = op.timestamp().as_micros() - REPORT_RATE_WINDOW;
+    let recent_count = previous_reports
+        .iter()
+        .filter(|(_, timestamp, _)| timestamp.as_micros() > window_start)
+        .count();
+
+    if recent_count >= REPORT_RATE_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} may not create more than {} reports within 24 hours",
+            entry.author, REPORT_RATE_LIMIT
+        )));
+    }
+
+    if previous_reports
+        .iter()
+        .any(|(_, _, report)| report.target == entry.target)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} already reported {:?}",
+            entry.author, entry.target
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_report_update(
+    _op: &Op,
+    _entry: ReportEntry,
+    _prev_entry: ReportEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "ReportEntry cannot be updated".to_string(),
+    ))
+}
+
+fn validate_report_delete(op: &Op, entry: ReportEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() && !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the report author or a steward can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Release
+//
+fn validate_common_release_fields(
+    _op: &Op,
+    entry: &ReleaseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.version.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "ReleaseEntry version cannot be empty".to_string(),
+        ));
+    }
+
+    if let Err(message) = entry.holochain_requirement() {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let Err(message) = validate_role_manifests(&entry.roles) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ReleaseEntry roles are invalid: {}",
+            message
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_release_create(op: &Op, entry: ReleaseEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.yanked.is_some() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "ReleaseEntry cannot be created as yanked".to_string(),
+        ));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_release_fields(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    let app: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    if !app.editors.contains(&entry.author) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} is not an editor of app {}",
+            entry.author, entry.app
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_release_update(
+    op: &Op,
+    entry: ReleaseEntry,
+    prev_entry: ReleaseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.yanked != prev_entry.yanked {
+        if let ValidateCallbackResult::Invalid(message) =
+            validate_release_yank(op, &entry, &prev_entry)?
+        {
+            return Ok(ValidateCallbackResult::Invalid(message));
+        }
+
+        // Editors other than the release author may only change the yank status
+        if &prev_entry.author != op.author() {
+            return Ok(ValidateCallbackResult::Valid);
+        }
+    }
+
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.app != prev_entry.app {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Cannot change release app: {} => {}",
+            prev_entry.app, entry.app
+        )));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_release_fields(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Yanking or unyanking is restricted to app editors and, unless they authored the release, may not
+/// change anything else
+fn validate_release_yank(
+    op: &Op,
+    entry: &ReleaseEntry,
+    prev_entry: &ReleaseEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    let app_action = match &entry.yank_app_action {
+        Some(app_action) => app_action,
+        None => {
+            return Ok(ValidateCallbackResult::Invalid(
+                "Yanking or unyanking a release must reference the app revision".to_string(),
+            ))
+        }
+    };
+    let app: AppEntry = must_get_valid_revision(&entry.app, app_action, "AppEntry")?;
+
+    if !app.editors.contains(op.author()) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only editors of app {} can yank or unyank its releases",
+            entry.app
+        )));
+    }
+
+    if let Some(yanked) = &entry.yanked {
+        if yanked.reason.trim().is_empty() {
+            return Ok(ValidateCallbackResult::Invalid(
+                "Yanking a release requires a reason".to_string(),
+            ));
+        }
+
+        if yanked.reason.chars().count() > YANK_REASON_LIMIT {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Yank reason may not exceed {} characters",
+                YANK_REASON_LIMIT
+            )));
+        }
+
+        if &yanked.yanked_by != op.author() {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Yank notice must name the agent yanking the release: {} != {}",
+                yanked.yanked_by,
+                op.author()
+            )));
+        }
+    }
+
+    let mut unchanged = entry.clone();
+    unchanged.yanked = prev_entry.yanked.clone();
+    unchanged.yank_app_action = prev_entry.yank_app_action.clone();
+    unchanged.last_updated = prev_entry.last_updated;
+
+    if &prev_entry.author != op.author() && &unchanged != prev_entry {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} can only change the yank status of release {}",
+            op.author(),
+            entry.version
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_release_delete(op: &Op, entry: ReleaseEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author == op.author() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let app: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    if !app.editors.contains(op.author()) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the release author or editors of app {} can delete release {}",
+            entry.app, entry.version
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Delisting
+//
+fn validate_common_delisting_fields(
+    op: &Op,
+    entry: &DelistingEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only stewards can manage delistings; {} is not a steward",
+            op.author()
+        )));
+    }
+
+    if entry.reason.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "DelistingEntry must give a reason".to_string(),
+        ));
+    }
+
+    if entry.reason.chars().count() > DELISTING_REASON_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "DelistingEntry reason may not exceed {} characters",
+            DELISTING_REASON_LIMIT
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_delisting_create(
+    op: &Op,
+    entry: DelistingEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_delisting_fields(op, &entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    match &entry.target {
+        DelistingTarget::App(id) => {
+            let _: AppEntry = must_get_valid_app_entry(id, "AppEntry")?;
+        }
+        DelistingTarget::Release(id) => {
+            let _: ReleaseEntry = must_get_valid_app_entry(id, "ReleaseEntry")?;
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_delisting_update(
+    op: &Op,
+    entry: DelistingEntry,
+    prev_entry: DelistingEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.target != prev_entry.target {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Cannot change delisting target: {:?} => {:?}",
+            prev_entry.target, entry.target
+        )));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_delisting_fields(op, &entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_delisting_delete(
+    op: &Op,
+    _entry: DelistingEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only stewards can revoke a delisting; {} is not a steward",
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Collection
+//
+fn validate_common_collection_fields(
+    op: &Op,
+    entry: &CollectionEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.title.trim().is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "CollectionEntry title cannot be empty".to_string(),
+        ));
+    }
+
+    if entry.title.chars().count() > COLLECTION_TITLE_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "CollectionEntry title may not exceed {} characters",
+            COLLECTION_TITLE_LIMIT
+        )));
+    }
+
+    if entry.apps.len() > COLLECTION_APPS_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "CollectionEntry may not have more than {} apps",
+            COLLECTION_APPS_LIMIT
+        )));
+    }
+
+    for (index, app) in entry.apps.iter().enumerate() {
+        if entry.apps[..index].contains(app) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "CollectionEntry lists app {} more than once",
+                app
+            )));
+        }
+    }
+
+    if entry.official != is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(
+            "CollectionEntry can only be official when its owner is a steward".to_string(),
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_collection_create(
+    op: &Op,
+    entry: CollectionEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_collection_fields(op, &entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_collection_update(
+    op: &Op,
+    entry: CollectionEntry,
+    prev_entry: CollectionEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_collection_fields(op, &entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_collection_delete(
+    op: &Op,
+    entry: CollectionEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() && !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the collection owner or a steward can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Endorsement
+//
+fn validate_endorsement_create(
+    op: &Op,
+    entry: EndorsementEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if !verify_signature_raw(
+        entry.author.to_owned(),
+        entry.signature.to_owned(),
+        entry.publisher.get_raw_39().to_vec(),
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "EndorsementEntry signature is not a signature of publisher {} by {}",
+            entry.publisher, entry.author
+        )));
+    }
+
+    let publisher: PublisherEntry = must_get_valid_app_entry(&entry.publisher, "PublisherEntry")?;
+
+    if publisher.author == entry.author || publisher.editors.contains(&entry.author) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Editors cannot endorse their own publisher".to_string(),
+        ));
+    }
+
+    let previous_endorsements: Vec<(ActionHash, Timestamp, EndorsementEntry)> =
+        get_chain_entries(op, EntryTypesUnit::Endorsement, true)?;
+
+    if previous_endorsements
+        .iter()
+        .any(|(_, _, endorsement)| endorsement.publisher == entry.publisher)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} already endorses publisher {}",
+            entry.author, entry.publisher
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_endorsement_update(
+    _op: &Op,
+    _entry: EndorsementEntry,
+    _prev_entry: EndorsementEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "EndorsementEntry cannot be updated; revoke it instead".to_string(),
+    ))
+}
+
+fn validate_endorsement_delete(
+    op: &Op,
+    entry: EndorsementEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the endorsement author can revoke it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Endorsement Link
+//
+// Endorsements are linked from their publisher's and their author's endorsement paths, and only by
+// their author, so nobody can attribute an endorsement to another agent or publisher.
+fn validate_endorsement_link_create(
+    op: &Op,
+    create_link: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    let id = match create_link.target_address.to_owned().into_action_hash() {
+        Some(id) => id,
+        None => {
+            return Ok(ValidateCallbackResult::Invalid(
+                "An endorsement link must target an endorsement".to_string(),
+            ))
+        }
+    };
+    let endorsement: EndorsementEntry = must_get_valid_app_entry(&id, "EndorsementEntry")?;
+
+    if &endorsement.author != op.author() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the endorsement author can link it: {} != {}",
+            endorsement.author,
+            op.author()
+        )));
+    }
+
+    let (_, publisher_path) = hc_utils::path(
+        ANCHOR_PUBLISHERS,
+        vec![
+            endorsement.publisher.to_string(),
+            ANCHOR_ENDORSEMENTS.to_string(),
+        ],
+    );
+    let (_, agent_path) = hc_utils::path(
+        ANCHOR_AGENTS,
+        vec![
+            endorsement.author.to_string(),
+            ANCHOR_ENDORSEMENTS.to_string(),
+        ],
+    );
+
+    if create_link.base_address != AnyLinkableHash::from(publisher_path)
+        && create_link.base_address != AnyLinkableHash::from(agent_path)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Endorsement {} can only be linked from its publisher's or author's endorsements",
+            id
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_endorsement_link_delete(
+    op: &Op,
+    create_link: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if op.author() != &create_link.author {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "An endorsement link can only be removed by its author ({})",
+            create_link.author
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Verification
+//
+fn validate_verification_create(
+    op: &Op,
+    entry: VerificationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only stewards can verify publishers; {} is not a steward",
+            op.author()
+        )));
+    }
+
+    if website_domain(&entry.domain).as_ref() != Some(&entry.domain) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "VerificationEntry domain '{}' must be a lowercase host name",
+            entry.domain
+        )));
+    }
+
+    let publisher: PublisherEntry =
+        must_get_valid_revision(&entry.publisher, &entry.publisher_action, "PublisherEntry")?;
+
+    if website_domain(&publisher.website.url).as_ref() != Some(&entry.domain) {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "VerificationEntry domain '{}' does not match the website of publisher {}: {}",
+            entry.domain, entry.publisher, publisher.website.url
+        )));
+    }
+
+    if publisher.author != entry.publisher_agent
+        && !publisher.editors.contains(&entry.publisher_agent)
+    {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} is not an editor of publisher {}",
+            entry.publisher_agent, entry.publisher
+        )));
+    }
+
+    let challenge = verification_challenge(&entry.publisher_agent, &entry.publisher, &entry.domain);
+    if !verify_signature_raw(
+        entry.publisher_agent.to_owned(),
+        entry.signature.to_owned(),
+        challenge.into_bytes(),
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "VerificationEntry signature is not a signature of the challenge by {}",
+            entry.publisher_agent
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_verification_update(
+    _op: &Op,
+    _entry: VerificationEntry,
+    _prev_entry: VerificationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "VerificationEntry cannot be updated; revoke it and verify again".to_string(),
+    ))
+}
+
+fn validate_verification_delete(
+    op: &Op,
+    _entry: VerificationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only stewards can revoke a verification; {} is not a steward",
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Install Link
+//
+fn validate_install_link_create(
+    op: &Op,
+    create_link: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if create_link.target_address != AnyLinkableHash::from(create_link.author.to_owned()) {
+        return Ok(ValidateCallbackResult::Invalid(
+            "An install link must target its author".to_string(),
+        ));
+    }
+
+    if !create_link.tag.0.is_empty() {
+        let release = match ActionHash::from_raw_39(create_link.tag.0.to_owned()) {
+            Ok(release) => release,
+            Err(_) => {
+                return Ok(ValidateCallbackResult::Invalid(
+                    "An install link tag must be empty or a release id".to_string(),
+                ))
+            }
+        };
+        let release_entry: ReleaseEntry = must_get_valid_app_entry(&release, "ReleaseEntry")?;
+        let (_, app_installs_path) = hc_utils::path(
+            ANCHOR_APPS,
+            vec![release_entry.app.to_string(), ANCHOR_INSTALLS.to_string()],
+        );
+
+        if create_link.base_address != AnyLinkableHash::from(app_installs_path) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Release {} does not belong to the app whose installs are being recorded",
+                release
+            )));
+        }
+    }
+
+    let duplicate = get_chain_links(op, LinkTypes::Install, true)?
+        .into_iter()
+        .any(|(_, previous)| previous.base_address == create_link.base_address);
+
+    if duplicate {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} has already recorded an install for base {}",
+            create_link.author, create_link.base_address
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_install_link_delete(
+    op: &Op,
+    create_link: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if op.author() != &create_link.author {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "An install link can only be removed by its author ({})",
+            create_link.author
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Favorite
+//
+fn validate_favorite_create(
+    _op: &Op,
+    entry: FavoriteEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    let _app: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_favorite_update(
+    _op: &Op,
+    _entry: FavoriteEntry,
+    _prev_entry: FavoriteEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "FavoriteEntry cannot be updated".to_string(),
+    ))
+}
+
+fn validate_favorite_delete(
+    _op: &Op,
+    _entry: FavoriteEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    // Only reached by the author since the entry content is private
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// App Draft
+//
+// Drafts are private and may be incomplete; the listing is validated as an AppEntry when the draft
+// is published.
+fn validate_app_draft_create(
+    _op: &Op,
+    entry: AppDraftEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.screenshots.len() > SCREENSHOT_COUNT_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "AppDraftEntry cannot have more than {} screenshots",
+            SCREENSHOT_COUNT_LIMIT
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_app_draft_update(
+    op: &Op,
+    entry: AppDraftEntry,
+    _prev_entry: AppDraftEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_app_draft_create(op, entry)
+}
+
+fn validate_app_draft_delete(
+    _op: &Op,
+    _entry: AppDraftEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Scheduled Publication
+//
+fn validate_scheduled_publication_create(
+    _op: &Op,
+    entry: ScheduledPublicationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.publish_at < entry.created_at {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ScheduledPublicationEntry publish time ({}) cannot be before its creation ({})",
+            entry.publish_at, entry.created_at
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Updates only record a failed attempt; anything else requires canceling and scheduling again
+fn validate_scheduled_publication_update(
+    _op: &Op,
+    entry: ScheduledPublicationEntry,
+    prev_entry: ScheduledPublicationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if entry.target != prev_entry.target
+        || entry.publish_at != prev_entry.publish_at
+        || entry.created_at != prev_entry.created_at
+    {
+        return Ok(ValidateCallbackResult::Invalid(
+            "ScheduledPublicationEntry cannot be changed; cancel it and schedule again".to_string(),
+        ));
+    }
+
+    if prev_entry.has_failed() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "ScheduledPublicationEntry has already failed".to_string(),
+        ));
+    }
+
+    if entry.attempts != prev_entry.attempts + 1 {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "ScheduledPublicationEntry attempts must increase by one ({} -> {})",
+            prev_entry.attempts, entry.attempts
+        )));
+    }
+
+    if entry.last_error.is_none() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "ScheduledPublicationEntry update must record the error of the failed attempt"
+                .to_string(),
+        ));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_scheduled_publication_delete(
+    _op: &Op,
+    _entry: ScheduledPublicationEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Channel Preference
+//
+fn validate_channel_preference_create(
+    _op: &Op,
+    entry: ChannelPreferenceEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    let _app: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_channel_preference_update(
+    _op: &Op,
+    _entry: ChannelPreferenceEntry,
+    _prev_entry: ChannelPreferenceEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(
+        "ChannelPreferenceEntry cannot be updated; delete it and create a new one".to_string(),
+    ))
+}
+
+fn validate_channel_preference_delete(
+    _op: &Op,
+    _entry: ChannelPreferenceEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    // Only reached by the author since the entry content is private
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Comment
+//
+fn validate_comment_text(text: &str) -> ValidateCallbackResult {
+    if text.trim().is_empty() {
+        return ValidateCallbackResult::Invalid("CommentEntry text cannot be empty".to_string());
+    }
+
+    if text.chars().count() > COMMENT_TEXT_LIMIT {
+        return ValidateCallbackResult::Invalid(format!(
+            "CommentEntry text may not exceed {} characters",
+            COMMENT_TEXT_LIMIT
+        ));
+    }
+
+    ValidateCallbackResult::Valid
+}
+
+fn validate_comment_create(op: &Op, entry: CommentEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_comment_text(&entry.text) {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    let _: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    if let Some(parent_id) = &entry.parent {
+        let parent: CommentEntry = must_get_valid_app_entry(parent_id, "CommentEntry")?;
+
+        if parent.app != entry.app {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Reply must be on the same app as its parent comment: {} != {}",
+                entry.app, parent.app
+            )));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_comment_update(
+    op: &Op,
+    entry: CommentEntry,
+    prev_entry: CommentEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.app != prev_entry.app || entry.parent != prev_entry.parent {
+        return Ok(ValidateCallbackResult::Invalid(
+            "Cannot move a comment to another app or thread".to_string(),
+        ));
+    }
+
+    Ok(validate_comment_text(&entry.text))
+}
+
+fn validate_comment_delete(op: &Op, entry: CommentEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the comment author can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+// Replies are linked from their parent comment by their own authors, so only they can unlink them
+fn validate_comment_link_delete(
+    op: &Op,
+    create_link: CreateLink,
+) -> ExternResult<ValidateCallbackResult> {
+    if op.author() != &create_link.author {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "A comment link can only be removed by its author ({})",
+            create_link.author
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//
+// Advisory
+//
+fn validate_common_advisory_fields(entry: &AdvisoryEntry) -> ExternResult<ValidateCallbackResult> {
+    if entry.description.chars().count() > ADVISORY_DESCRIPTION_LIMIT {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "AdvisoryEntry description may not exceed {} characters",
+            ADVISORY_DESCRIPTION_LIMIT
+        )));
+    }
+
+    if entry.affected_versions.is_empty() {
+        return Ok(ValidateCallbackResult::Invalid(
+            "AdvisoryEntry must have at least 1 affected version range".to_string(),
+        ));
+    }
+
+    if let Err(message) = entry.affected_requirements() {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let Some(fixed_version) = &entry.fixed_version {
+        if let Err(message) = parse_version(fixed_version) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "AdvisoryEntry fixed version is invalid: {}",
+                message
+            )));
+        }
+
+        if entry.affects(fixed_version) {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "Fixed version {} cannot be within the affected version ranges",
+                fixed_version
+            )));
+        }
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_advisory_create(op: &Op, entry: AdvisoryEntry) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) = validate_common_fields_create(op, &entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if let ValidateCallbackResult::Invalid(message) = validate_common_advisory_fields(&entry)? {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    let app: AppEntry = must_get_valid_app_entry(&entry.app, "AppEntry")?;
+
+    if !app.editors.contains(&entry.author) && !is_steward(&entry.author)? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Agent {} is not an editor of app {} or a steward",
+            entry.author, entry.app
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_advisory_update(
+    op: &Op,
+    entry: AdvisoryEntry,
+    prev_entry: AdvisoryEntry,
+) -> ExternResult<ValidateCallbackResult> {
+    if let ValidateCallbackResult::Invalid(message) =
+        validate_common_fields_update(op, &entry, &prev_entry)?
+    {
+        return Ok(ValidateCallbackResult::Invalid(message));
+    }
+
+    if entry.app != prev_entry.app {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Cannot change advisory app: {} => {}",
+            prev_entry.app, entry.app
+        )));
+    }
+
+    validate_common_advisory_fields(&entry)
+}
+
+fn validate_advisory_delete(op: &Op, entry: AdvisoryEntry) -> ExternResult<ValidateCallbackResult> {
+    if &entry.author != op.author() && !is_steward(op.author())? {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "Only the advisory author or a steward can delete it: {} != {}",
+            entry.author,
+            op.author()
+        )));
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}